@@ -1,4 +1,5 @@
 use avian2d::prelude::PhysicsSystems;
+use avian2d::prelude::Position;
 use bevy::prelude::*;
 
 // Translation Attachment subsystem.
@@ -12,6 +13,11 @@ impl Plugin for AttachPlugin {
         app.add_systems(
             FixedPostUpdate,
             propagate_attachment_translation.after(PhysicsSystems::Writeback),
+        )
+        // Attachments with a collider (ie shield) needs to be moved before the physics step
+        .add_systems(
+            FixedPostUpdate,
+            propagate_attachment_position.before(PhysicsSystems::First),
         );
     }
 }
@@ -39,3 +45,15 @@ fn propagate_attachment_translation(
         }
     }
 }
+
+// Physics attachments sits on the ship center, the collider shape handles any offset
+fn propagate_attachment_position(
+    mut query: Query<(&AttachedTo, &mut Position)>,
+    parent_query: Query<&Position, Without<AttachedTo>>,
+) {
+    for (attached_to, mut position) in query.iter_mut() {
+        if let Ok(parent_position) = parent_query.get(attached_to.0) {
+            position.0 = parent_position.0;
+        }
+    }
+}
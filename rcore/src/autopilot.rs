@@ -0,0 +1,359 @@
+use avian2d::prelude::*;
+use bevy::math::I64Vec2;
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::math::AbsRot;
+use crate::movement::Rcs;
+use crate::movement::Thrust;
+use crate::nav;
+use crate::rotation::Heading;
+use crate::rotation::TargetHeading;
+use crate::rotation::apply_rotation;
+use crate::sleep::Sleep;
+
+// Autopilot subsystem:
+// - The script hands the autopilot an order, it then flies the ship every tick (heading and main
+//   engine) till the script gives it a new order or takes the controls back.
+// - Orders: fly to a point, match a target velocity, orbit a target at a range, hold position.
+// - Once the order is reached the script gets notified, the autopilot keeps at it (station keeping
+//   at the point, matching or orbiting the target). If the target is gone the order is dropped.
+// - Turn and burn, the ship points the engine along the velocity change it wants and only burns
+//   once it's close enough to the heading, it brakes early enough to flip around.
+pub struct AutopilotPlugin;
+impl Plugin for AutopilotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<AutopilotMessage>().add_systems(
+            FixedUpdate,
+            apply_autopilot
+                .before(apply_rotation)
+                .in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+// Close enough to the point or orbit
+pub const ARRIVAL_RANGE: i64 = 100;
+// Slow enough to count as stopped or matched
+pub const ARRIVAL_SPEED: i64 = 5;
+// Ignore velocity errors below this, keeps the ship from chasing its tail
+const DEADBAND: i64 = 2;
+// Half-arc off the wanted heading the engine is allowed to burn at
+const BURN_ARC: u8 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutopilotOrder {
+    // Fly to the point and stop there
+    FlyTo(IVec2),
+    // Match the target velocity
+    MatchVelocity(Entity),
+    // Circle the target at the range, negative ranges are taken as 0
+    Orbit(Entity, i64),
+    // Stop and keep station where the order was given
+    Hold,
+}
+
+// Flight computer, every ship has one
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Autopilot {
+    pub order: Option<AutopilotOrder>,
+    // Main engine acceleration the autopilot flies with
+    pub thrust: i32,
+    // Where to hold, set on the first tick of the hold
+    pub station: Option<IVec2>,
+    // Order reached and the script was told
+    pub reached: bool,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self {
+            order: None,
+            thrust: 20,
+            station: None,
+            reached: false,
+        }
+    }
+}
+
+impl Autopilot {
+    // New order, or None to hand the controls back to the script
+    pub fn engage(&mut self, order: Option<AutopilotOrder>) {
+        // Scripts hand in any range, the orbit math needs it positive
+        self.order = order.map(|order| match order {
+            AutopilotOrder::Orbit(target, range) => AutopilotOrder::Orbit(target, range.max(0)),
+            order => order,
+        });
+        self.station = None;
+        self.reached = false;
+    }
+}
+
+// Order reached
+// 0 - ship
+// 1 - order
+#[derive(Message, Copy, Clone, Debug)]
+pub struct AutopilotMessage(pub Entity, pub AutopilotOrder);
+
+// Velocity to close `offset` at, fast as possible but slow enough to flip around (turn ticks) and
+// brake to a stop on arrival. Stays put if the ship can't turn (None), it could never brake.
+fn approach_velocity(offset: IVec2, thrust: i32, turn_ticks: Option<u64>, max_speed: i64) -> IVec2 {
+    let Some(turn_ticks) = turn_ticks else {
+        return IVec2::ZERO;
+    };
+    let distance = nav::range(IVec2::ZERO, offset);
+    if distance <= ARRIVAL_RANGE / 2 {
+        return IVec2::ZERO;
+    }
+
+    // Coast for the flip then brake: v * t + v^2 / 2a = d, wide enough for the slowest turns
+    let thrust = i128::from(thrust.max(0));
+    let flip = thrust * i128::from(turn_ticks) / i128::from(TICK_HZ);
+    let braking = flip
+        .saturating_pow(2)
+        .saturating_add(2 * thrust * i128::from(distance));
+    let speed = (braking.isqrt() - flip).clamp(0, i128::from(max_speed.max(0)));
+    let speed = i64::try_from(speed).unwrap_or(0);
+
+    (offset.as_i64vec2() * speed / distance).as_ivec2()
+}
+
+// Heading and engine acceleration to take velocity to the wanted velocity, None for the heading
+// if the ship is close enough already
+fn steer(velocity: IVec2, wanted: IVec2, heading: AbsRot, thrust: i32) -> (Option<AbsRot>, i32) {
+    let error = wanted - velocity;
+    let magnitude = nav::range(IVec2::ZERO, error);
+    if magnitude <= DEADBAND {
+        return (None, 0);
+    }
+
+    let bearing = nav::bearing(IVec2::ZERO, error);
+    let acceleration = if bearing.is_some_and(|bearing| heading.within(BURN_ARC, bearing)) {
+        // Ease off as the error closes in
+        i32::try_from(magnitude * 8).map_or(thrust, |eased| eased.min(thrust))
+    } else {
+        0
+    };
+    (bearing, acceleration)
+}
+
+#[expect(clippy::type_complexity)]
+fn apply_autopilot(
+    mut message: MessageWriter<AutopilotMessage>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Autopilot,
+            &Position,
+            &LinearVelocity,
+            &Heading,
+            &mut TargetHeading,
+            &mut Thrust,
+            Option<&mut Rcs>,
+        ),
+        Without<Sleep>,
+    >,
+    target_query: Query<(&Position, &LinearVelocity)>,
+) {
+    for (entity, mut autopilot, pos, velocity, heading, mut target_heading, mut thrust, rcs) in
+        query.iter_mut()
+    {
+        let Some(order) = autopilot.order else {
+            continue;
+        };
+
+        let pos = pos.0.as_ivec2();
+        let velocity = velocity.0.as_ivec2();
+        let stopped = nav::range(IVec2::ZERO, velocity) <= ARRIVAL_SPEED;
+
+        // Lorentz factor makes the top end crawl, stay clear of it
+        let max_speed = i64::from(thrust.velocity_limit) * 3 / 4;
+        let turn_ticks = nav::time_to_turn(AbsRot(0), AbsRot(128), target_heading.limit);
+        let autopilot_thrust = autopilot.thrust;
+        let approach = |offset| approach_velocity(offset, autopilot_thrust, turn_ticks, max_speed);
+
+        let target = match order {
+            AutopilotOrder::MatchVelocity(target) | AutopilotOrder::Orbit(target, _) => {
+                let Ok((target_pos, target_velocity)) = target_query.get(target) else {
+                    // Target is gone, nothing left to do
+                    autopilot.engage(None);
+                    thrust.acceleration = 0;
+                    continue;
+                };
+                (target_pos.0.as_ivec2(), target_velocity.0.as_ivec2())
+            }
+            _ => (IVec2::ZERO, IVec2::ZERO),
+        };
+
+        let (wanted, reached) = match order {
+            AutopilotOrder::FlyTo(point) => (
+                approach(point - pos),
+                stopped && nav::range(pos, point) <= ARRIVAL_RANGE,
+            ),
+            AutopilotOrder::Hold => {
+                let station = *autopilot.station.get_or_insert(pos);
+                (
+                    approach(station - pos),
+                    stopped && nav::range(pos, station) <= ARRIVAL_RANGE,
+                )
+            }
+            AutopilotOrder::MatchVelocity(_) => {
+                let (_, target_velocity) = target;
+                (
+                    target_velocity,
+                    nav::range(velocity, target_velocity) <= ARRIVAL_SPEED,
+                )
+            }
+            AutopilotOrder::Orbit(_, range) => {
+                let (target_pos, target_velocity) = target;
+                let distance = nav::range(target_pos, pos).max(1);
+                let radial = if pos == target_pos {
+                    I64Vec2::Y
+                } else {
+                    (pos - target_pos).as_i64vec2()
+                };
+
+                // Half the thrust holds the turn (v^2 / r), the rest corrects the range
+                let orbit_speed = (i64::from(autopilot_thrust) * range / 2)
+                    .isqrt()
+                    .min(max_speed);
+                let tangent = I64Vec2::new(radial.y, -radial.x) * orbit_speed / distance;
+                let correction = approach((radial * (range - distance) / distance).as_ivec2());
+
+                (
+                    target_velocity + tangent.as_ivec2() + correction,
+                    (distance - range).abs() <= ARRIVAL_RANGE,
+                )
+            }
+        };
+
+        let (bearing, acceleration) = steer(velocity, wanted, heading.0, autopilot.thrust);
+        if let Some(bearing) = bearing {
+            target_heading.target = bearing;
+        }
+        thrust.acceleration = acceleration;
+        // Autopilot flies on the main engine
+        if let Some(mut rcs) = rcs {
+            rcs.command(IVec2::ZERO);
+        }
+
+        if reached && !autopilot.reached {
+            autopilot.reached = true;
+            message.write(AutopilotMessage(entity, order));
+        }
+    }
+}
+
+#[test]
+fn test_approach_velocity() {
+    // Close enough, stop
+    assert_eq!(
+        approach_velocity(IVec2::new(0, 10), 20, Some(0), 100),
+        IVec2::ZERO
+    );
+
+    // Instant flip: v^2 = 2ad -> sqrt(2 * 20 * 1000) = 200, capped at 75
+    assert_eq!(
+        approach_velocity(IVec2::new(0, 1000), 20, Some(0), 75),
+        IVec2::new(0, 75)
+    );
+    assert_eq!(
+        approach_velocity(IVec2::new(0, -250), 20, Some(0), 200),
+        IVec2::new(0, -100)
+    );
+
+    // 8 second flip: 8v + v^2 / 40 = 1000 -> v = 96
+    assert_eq!(
+        approach_velocity(IVec2::new(1000, 0), 20, Some(512), 200),
+        IVec2::new(96, 0)
+    );
+
+    // Can't turn so it could never brake, stay put
+    assert_eq!(
+        approach_velocity(IVec2::new(1000, 0), 20, None, 200),
+        IVec2::ZERO
+    );
+    // Slowest turn there is with everything maxed out, still no overflow
+    assert_eq!(
+        approach_velocity(IVec2::MAX, i32::MAX, Some(u64::MAX), i64::MAX),
+        IVec2::ZERO
+    );
+}
+
+#[test]
+fn test_engage_orbit_range() {
+    let target = Entity::from_raw_u32(1).expect("entity");
+    let mut autopilot = Autopilot::default();
+    autopilot.engage(Some(AutopilotOrder::Orbit(target, -500)));
+    assert_eq!(autopilot.order, Some(AutopilotOrder::Orbit(target, 0)));
+}
+
+#[test]
+fn test_apply_autopilot() {
+    use crate::ship::ShipBuilder;
+    use crate::ship::add_ship;
+    use crate::testing::idle;
+    use crate::testing::sim_app;
+
+    let mut app = sim_app();
+    let ship = ShipBuilder::new(idle()).build();
+    let ship = add_ship(&mut app.world_mut().commands(), ship);
+    app.update();
+
+    let point = IVec2::new(0, 2000);
+    app.world_mut()
+        .get_mut::<Autopilot>(ship)
+        .expect("autopilot")
+        .engage(Some(AutopilotOrder::FlyTo(point)));
+
+    let mut cursor = app
+        .world()
+        .resource::<Messages<AutopilotMessage>>()
+        .get_cursor();
+    let mut reached = vec![];
+    for _ in 0..TICK_HZ * 120 {
+        app.update();
+        let messages = app.world().resource::<Messages<AutopilotMessage>>();
+        reached.extend(cursor.read(messages).map(|message| (message.0, message.1)));
+        if !reached.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(reached, [(ship, AutopilotOrder::FlyTo(point))]);
+
+    // Stopped on the point
+    let pos = app.world().get::<Position>(ship).expect("position");
+    assert!(nav::range(pos.0.as_ivec2(), point) <= ARRIVAL_RANGE);
+    let velocity = app.world().get::<LinearVelocity>(ship).expect("velocity");
+    assert!(nav::range(IVec2::ZERO, velocity.0.as_ivec2()) <= ARRIVAL_SPEED);
+}
+
+#[test]
+fn test_steer() {
+    // On target
+    assert_eq!(
+        steer(IVec2::new(0, 50), IVec2::new(1, 50), AbsRot(0), 20),
+        (None, 0)
+    );
+
+    // Need to slow down, facing the wrong way
+    assert_eq!(
+        steer(IVec2::new(0, 50), IVec2::ZERO, AbsRot(0), 20),
+        (Some(AbsRot(128)), 0)
+    );
+
+    // Facing the right way, full burn then ease off
+    assert_eq!(
+        steer(IVec2::new(0, 50), IVec2::ZERO, AbsRot(128), 20),
+        (Some(AbsRot(128)), 20)
+    );
+    assert_eq!(
+        steer(IVec2::new(0, 1), IVec2::new(0, -1), AbsRot(128), 20),
+        (None, 0)
+    );
+    assert_eq!(
+        steer(IVec2::new(0, 2), IVec2::new(0, -1), AbsRot(128), 40),
+        (Some(AbsRot(128)), 24)
+    );
+}
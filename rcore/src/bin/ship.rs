@@ -2,17 +2,29 @@ use bevy::ecs::schedule::LogLevel;
 use bevy::ecs::schedule::ScheduleBuildSettings;
 use bevy::prelude::*;
 
+use std::path::PathBuf;
+
 use rcore::SimulationPlugin;
+use rcore::autopilot::AutopilotOrder;
+use rcore::fabrication::BuildOrder;
 use rcore::math::AbsRot;
 use rcore::math::RelRot;
+use rcore::mining::add_resource_field;
 use rcore::script::Script;
 use rcore::script::ShipAction;
+use rcore::script::ShipCollision;
 use rcore::script::ShipScript;
 use rcore::script::ShipStatus;
 use rcore::ship::DebugBuilder;
+use rcore::ship::Loadout;
 use rcore::ship::ShipBuilder;
+use rcore::ship::ShipClass;
 use rcore::ship::StarterShip;
 use rcore::ship::add_ship;
+use rcore::sleep::Sleep;
+use rcore::snapshot::SnapshotExport;
+use rcore::stats::StatsDump;
+use rcore::weapon::ShieldCurve;
 
 #[cfg(feature = "render")]
 use rcore::render::camera::{CameraMode, CameraRig};
@@ -33,6 +45,9 @@ fn main() {
         })
         // Startup ship resource for spawning initial ships
         .insert_resource(StartShip(ship_setup()))
+        // Match stats gets written out on exit
+        .insert_resource(StatsDump(PathBuf::from("match_stats.json")))
+        .add_systems(Startup, add_resource_fields)
         .run();
 }
 
@@ -43,10 +58,21 @@ impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
         use rcore::render::RenderPlugin;
         use rcore::render::camera::{CameraPlugin, camera_setup};
+        use rcore::render::input::InputConfig;
 
         app.add_plugins(DefaultPlugins)
             .add_plugins(RenderPlugin)
             .add_plugins(CameraPlugin)
+            // Key bindings, the defaults are used if it's missing
+            .insert_resource(InputConfig(PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/input.cfg"
+            ))))
+            // Snapshots on demand (snapshot key)
+            .insert_resource(SnapshotExport {
+                dir: PathBuf::from("snapshots"),
+                every: None,
+            })
             .add_systems(Startup, add_ships.after(camera_setup));
     }
 
@@ -61,6 +87,11 @@ impl Plugin for SetupPlugin {
             Duration::from_secs_f64(1.0 / 60.0),
         )))
         .add_systems(Startup, add_ships)
+        // Snapshot every 10 seconds of sim time
+        .insert_resource(SnapshotExport {
+            dir: PathBuf::from("snapshots"),
+            every: Some(640),
+        })
         .add_systems(Startup, |mut writer: MessageWriter<TimeMsg>| {
             // 16x speedup
             writer.write(TimeMsg::Speed(4));
@@ -75,6 +106,7 @@ struct SimpleShip {
     dec: i32,
     rot: i8,
     collision: bool,
+    fabricated: bool,
     target_x: i32,
     target_y: i32,
     target_e: Option<Entity>,
@@ -92,6 +124,7 @@ impl SimpleShip {
             target_e: None,
 
             collision: false,
+            fabricated: false,
         }
     }
 }
@@ -108,6 +141,21 @@ impl ShipScript for SimpleShip {
             status.position, status.velocity, status.heading,
         );
 
+        // Build an escort once
+        if !self.fabricated {
+            self.fabricated = true;
+            return ShipAction::new().fabricate(BuildOrder::new(
+                ShipClass::Small,
+                Loadout::new().shield(false),
+                Box::new(PatrolShip::new()),
+            ));
+        }
+
+        // Missile ran dry, coast till close to the enemy
+        if status.propellant == Some(0) {
+            return ShipAction::new().sleep(Sleep::timer(640).or_proximity(1000));
+        }
+
         if status.heading == AbsRot(0) || status.heading == AbsRot(128) {
             if status.velocity.y < 95 && status.heading == AbsRot(0) {
                 println!("Accelerate");
@@ -145,9 +193,57 @@ impl ShipScript for SimpleShip {
         self.target_e = Some(target_entity);
     }
 
-    fn on_collision(&mut self) {
+    fn on_build_complete(&mut self, class: ShipClass) {
+        println!("on_build_complete - {class:?}");
+    }
+
+    fn on_wake(&mut self) {
+        println!("on_wake");
+    }
+
+    fn on_collision(&mut self, collision: &ShipCollision) {
         self.collision = true;
-        println!("on_collision");
+        println!(
+            "on_collision - {:?} - speed: {:?}, damage: {:?}",
+            collision.target_class, collision.relative_speed, collision.damage
+        );
+    }
+}
+
+// Flies between waypoints on the autopilot
+#[derive(Clone)]
+struct PatrolShip {
+    waypoints: Vec<IVec2>,
+    next: usize,
+    engaged: bool,
+}
+
+impl PatrolShip {
+    fn new() -> Self {
+        Self {
+            waypoints: vec![IVec2::new(1500, 1500), IVec2::new(-1500, 1500)],
+            next: 0,
+            engaged: false,
+        }
+    }
+}
+
+impl ShipScript for PatrolShip {
+    fn on_update(&mut self, _status: &ShipStatus) -> ShipAction {
+        if self.engaged {
+            return ShipAction::new();
+        }
+        self.engaged = true;
+        ShipAction::new().autopilot(AutopilotOrder::FlyTo(self.waypoints[self.next]))
+    }
+    fn on_contact(&mut self, _target_pos: IVec2, _target_entity: Entity) {}
+    fn on_collision(&mut self, _collision: &ShipCollision) {}
+
+    // Waypoint reached, on to the next one
+    fn on_autopilot(&mut self, order: AutopilotOrder) {
+        println!("on_autopilot - {order:?}");
+        self.next = (self.next + 1) % self.waypoints.len();
+        self.engaged = false;
     }
 }
 
@@ -160,7 +256,11 @@ impl ShipScript for DummyShip {
         ShipAction::new()
     }
     fn on_contact(&mut self, _target_pos: IVec2, _target_entity: Entity) {}
-    fn on_collision(&mut self) {}
+    fn on_collision(&mut self, _collision: &ShipCollision) {}
+
+    fn on_destroyed(&mut self) {
+        println!("on_destroyed");
+    }
 }
 
 #[derive(Resource)]
@@ -180,6 +280,8 @@ fn ship_setup() -> Vec<StarterShip> {
         .rotation_limit(16)
         .radar(AbsRot(0))
         .radar_arc(32)
+        .loadout(Loadout::new().fabricator(true))
+        .cargo(200)
         .debug(
             DebugBuilder::new()
                 .radar()
@@ -195,11 +297,13 @@ fn ship_setup() -> Vec<StarterShip> {
         ShipBuilder::new(Script {
             script: Box::new(DummyShip),
         })
+        .faction(1)
         .position(3500, 0)
         .velocity(0, 0)
         .radar_arc(1)
         .shield(AbsRot(192))
-        .shield_damage_reduce(0.75)
+        .shield_curve(ShieldCurve::new(0.95, 0.5, 1.0))
+        .reactor(150, 1200)
         .debug(
             DebugBuilder::new()
                 .health()
@@ -211,11 +315,14 @@ fn ship_setup() -> Vec<StarterShip> {
         ShipBuilder::new(Script {
             script: Box::new(DummyShip),
         })
+        .class(ShipClass::Large)
+        .faction(1)
         .position(-3500, 0)
         .velocity(0, 0)
         .radar_arc(1)
         .shield(AbsRot(0))
-        .shield_damage_reduce(0.25)
+        .shield_curve(ShieldCurve::new(0.5, 0.0, 1.0))
+        .shield_regen(8, 2, 256)
         .debug(
             DebugBuilder::new()
                 .health()
@@ -227,13 +334,22 @@ fn ship_setup() -> Vec<StarterShip> {
         ShipBuilder::new(Script {
             script: Box::new(DummyShip),
         })
+        .class(ShipClass::Small)
+        .faction(1)
         .position(-4500, -2500)
         .velocity(0, 0)
         .radar_arc(1)
+        .loadout(Loadout::new().weapon(false).shield(false).mining(true))
         .build(),
     ]
 }
 
+fn add_resource_fields(mut commands: Commands) {
+    add_resource_field(&mut commands, -4500, -2000, 5000);
+    add_resource_field(&mut commands, 2000, 2500, 10000);
+    add_resource_field(&mut commands, -1500, -3000, 2500);
+}
+
 #[expect(clippy::explicit_iter_loop)]
 #[cfg(not(feature = "render"))]
 fn add_ships(ships: Res<StartShip>, mut commands: Commands) {
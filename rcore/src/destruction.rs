@@ -0,0 +1,222 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::effect::Effect;
+use crate::impact::wreck_layers;
+use crate::script::Script;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::time::Ticks;
+use crate::weapon::DamageCause;
+use crate::weapon::DamageEvent;
+use crate::weapon::process_fire_debug_missile_message;
+
+// Ship destruction subsystem:
+// - A ship that hits 0 HP is marked `Destroyed`, any further damage that tick is ignored.
+// - Once all of the weapons for the tick are resolved the destroyed ships are processed in entity
+//   order, the script gets its `on_destroyed` call and the reactor (if any) blows up dealing area
+//   damage. Ships that gets caught in the blast are processed on the next tick.
+// - The ship is then replaced with a wreck, it keeps drifting and other hulls can still run into
+//   it, but it has no script, modules or health.
+pub struct DestructionPlugin;
+impl Plugin for DestructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            process_destroyed
+                .after(process_fire_debug_missile_message)
+                .in_set(FixedGameSystem::Weapon),
+        );
+    }
+}
+
+// Ship is at 0 HP and waiting on its despawn
+// 0 - who dealt the final blow
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Destroyed(pub Option<Entity>);
+
+// Reactor that goes up when the ship is destroyed
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Reactor {
+    pub damage: u16,
+    pub radius: i64,
+}
+
+// What is left of the ship
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Wreck(pub ShipClass);
+
+#[expect(clippy::type_complexity)]
+pub(crate) fn process_destroyed(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Ship,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &Transform,
+            Option<&mut Script>,
+            Option<&Reactor>,
+        ),
+        With<Destroyed>,
+    >,
+    target_query: Query<(Entity, &Position), (With<Ship>, Without<Destroyed>)>,
+    ticks: Res<Ticks>,
+) {
+    // Process in entity order to preserve replay orders
+    let mut destroyed: Vec<Entity> = query.iter().map(|(entity, ..)| entity).collect();
+    destroyed.sort();
+
+    for entity in destroyed {
+        let (_, ship, pos, rot, velocity, transform, script, reactor) =
+            query.get_mut(entity).expect("destroyed");
+
+        if let Some(mut script) = script {
+            script.script.on_destroyed();
+        }
+
+        if let Some(reactor) = reactor {
+            let base = pos.0.as_ivec2();
+            for (target, target_pos) in target_query.iter() {
+                if base
+                    .as_i64vec2()
+                    .distance_squared(target_pos.0.as_ivec2().as_i64vec2())
+                    <= reactor.radius.pow(2)
+                {
+                    commands.trigger(DamageEvent {
+                        target,
+                        pos: base,
+                        dmg: reactor.damage,
+                        source: Some(entity),
+                        cause: DamageCause::Reactor,
+                    });
+                }
+            }
+
+            commands.spawn(Effect::blast(
+                &ticks,
+                transform.translation.truncate(),
+                reactor.radius,
+            ));
+        }
+
+        commands.spawn((
+            Wreck(ship.0),
+            RigidBody::Kinematic,
+            *pos,
+            *rot,
+            *velocity,
+            *transform,
+            Collider::circle(ship.0.stats().collider_radius),
+            wreck_layers(),
+            CollisionEventsEnabled,
+        ));
+
+        // Takes the modules with it
+        commands.entity(entity).despawn();
+    }
+}
+
+#[test]
+fn test_process_destroyed() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+
+    use crate::impact::ship_layers;
+    use crate::script::ShipAction;
+    use crate::script::ShipCollision;
+    use crate::script::ShipScript;
+    use crate::script::ShipStatus;
+    use crate::ship::Faction;
+    use crate::ship::Loadout;
+    use crate::ship::ShipBuilder;
+    use crate::ship::add_ship;
+    use crate::testing::idle;
+    use crate::testing::sim_app;
+    use crate::weapon::Health;
+
+    #[derive(Clone)]
+    struct Doomed(Arc<AtomicU32>);
+    impl ShipScript for Doomed {
+        fn on_update(&mut self, _status: &ShipStatus) -> ShipAction {
+            ShipAction::new()
+        }
+        fn on_contact(&mut self, _target_pos: IVec2, _target_entity: Entity) {}
+        fn on_collision(&mut self, _collision: &ShipCollision) {}
+        fn on_destroyed(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut app = sim_app();
+    let destroyed = Arc::new(AtomicU32::new(0));
+    let victim = ShipBuilder::new(Script {
+        script: Box::new(Doomed(Arc::clone(&destroyed))),
+    })
+    .loadout(Loadout::new().shield(false))
+    .health(10)
+    .reactor(30, 1000)
+    .build();
+    let victim = add_ship(&mut app.world_mut().commands(), victim);
+    // One in the blast radius sturdy enough to live through it, one well outside of it
+    let [near, far] = [(0, 600), (0, 3000)].map(|(x, y)| {
+        let ship = ShipBuilder::new(idle())
+            .faction(1)
+            .loadout(Loadout::new().shield(false))
+            .position(x, y)
+            .health(100)
+            .build();
+        add_ship(&mut app.world_mut().commands(), ship)
+    });
+    // Right next to it and goes up in the blast
+    let caught = ShipBuilder::new(idle())
+        .faction(1)
+        .loadout(Loadout::new().shield(false))
+        .position(-600, 0)
+        .health(20)
+        .build();
+    let caught = add_ship(&mut app.world_mut().commands(), caught);
+    app.update();
+
+    app.world_mut().trigger(DamageEvent {
+        target: victim,
+        pos: IVec2::new(0, 100),
+        dmg: 10,
+        source: None,
+        cause: DamageCause::Weapon,
+    });
+    app.world_mut().flush();
+    assert!(app.world().get::<Destroyed>(victim).is_some());
+    app.update();
+
+    // Script got its last call and the ship is now a wreck
+    assert_eq!(destroyed.load(Ordering::Relaxed), 1);
+    assert!(app.world().get_entity(victim).is_err());
+    let (wreck_class, layers) = app
+        .world_mut()
+        .query_filtered::<(&Wreck, &CollisionLayers), With<Collider>>()
+        .single(app.world())
+        .map(|(wreck, layers)| (wreck.0, *layers))
+        .expect("wreck");
+    assert_eq!(wreck_class, ShipClass::Medium);
+    // Hulls still run into it
+    assert!(layers.interacts_with(ship_layers(ShipClass::Medium, Faction(1))));
+
+    // Blast hit everything in the radius and nothing outside of it
+    let health = |app: &App, ship| app.world().get::<Health>(ship).expect("health").current;
+    assert_eq!(health(&app, near), 70);
+    assert_eq!(health(&app, far), 100);
+    assert!(app.world().get::<Destroyed>(caught).is_some());
+
+    // Ships killed by the blast are wrecked on the next tick
+    app.update();
+    assert!(app.world().get_entity(caught).is_err());
+    assert_eq!(
+        app.world_mut().query::<&Wreck>().iter(app.world()).count(),
+        2
+    );
+}
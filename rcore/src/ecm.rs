@@ -0,0 +1,260 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::attach::AttachedTo;
+use crate::fabrication::apply_fabrication;
+use crate::math::AbsRot;
+use crate::math::tick_step;
+use crate::movement::Thrust;
+use crate::power::Power;
+use crate::radar::ArcCheck;
+use crate::radar::DISTANCE;
+use crate::radar::Radar;
+use crate::radar::RadarRange;
+use crate::radar::within_arc;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::sleep::Sleep;
+use crate::time::Ticks;
+
+// Electronic warfare subsystem:
+// - Active radars, burning engines, jammers and decoys give off emissions.
+// - Every ship has a passive sensor that picks up enemy emitters, it only gets the bearing to the
+//   emitter, not the range. Running the radar gives your position away to anyone listening.
+// - Jammer modules cover an arc off the bow, enemy radars inside the arc loses any contact further
+//   away than the burn through distance.
+// - Decoys are launched from the ship, they show up as contacts on enemy radars and as a radar
+//   emitter on passive sensors, then fizzle out after a while.
+pub struct EcmPlugin;
+impl Plugin for EcmPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<BearingMessage>().add_systems(
+            FixedUpdate,
+            (
+                // Shares the power bank with the shield, thrust and the fabrication bay
+                (apply_jammer, apply_passive_sensor)
+                    .chain()
+                    .after(apply_fabrication),
+                (apply_decoy_launch, apply_decoy).chain(),
+            )
+                .in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+// Distance an emission can be picked up from, radar pulses only have to make it one way so
+// passive sensors hear them at twice the radar range, well before the radar sees anything
+pub const RADAR_EMISSION_FACTOR: i64 = 2;
+pub const RADAR_EMISSION: i64 = DISTANCE * RADAR_EMISSION_FACTOR;
+pub const ENGINE_EMISSION: i64 = 3000;
+pub const JAMMER_EMISSION: i64 = DISTANCE * 3;
+
+// Decoys looks like a medium hull on radar
+pub const DECOY_SIGNATURE: u16 = 100;
+// 10 seconds
+pub const DECOY_LIFETIME: u64 = 640;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emission {
+    Radar,
+    Engine,
+    Jammer,
+}
+
+// Fixed forward facing jammer
+// - Toggled by the script, draws power every tick it is on
+// - No power, no jamming for that tick
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Jammer {
+    pub active: bool,
+    // On and powered this tick
+    pub jamming: bool,
+    // Half-arc off the bow
+    pub arc: u8,
+    pub range: i64,
+    // Distance the jammed radar can still see through the noise
+    pub burn_through: i64,
+    // Power per second while on
+    pub power_cost: u32,
+    // Sub tick power draw
+    pub carry: u32,
+}
+
+impl Default for Jammer {
+    fn default() -> Self {
+        Self {
+            active: false,
+            jamming: false,
+            arc: 16,
+            range: DISTANCE,
+            burn_through: DISTANCE / 4,
+            power_cost: 200,
+            carry: 0,
+        }
+    }
+}
+
+impl Jammer {
+    // Is the target inside the jammer arc and range
+    pub fn covers(&self, base: IVec2, heading: AbsRot, target: IVec2) -> bool {
+        base.as_i64vec2().distance_squared(target.as_i64vec2()) <= self.range.pow(2)
+            && matches!(
+                within_arc(base, target, heading, self.arc),
+                ArcCheck::InsideArc
+            )
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DecoyLauncher {
+    pub remaining: u8,
+    // Launch velocity relative to the ship, set by the script
+    pub launch: Option<IVec2>,
+}
+
+impl DecoyLauncher {
+    pub fn new(remaining: u8) -> Self {
+        Self {
+            remaining,
+            launch: None,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Decoy {
+    // Tick the decoy fizzles out
+    pub expires: u64,
+}
+
+// Passive sensor pickup
+// 0 - self
+// 1 - bearing to the emitter
+// 2 - type of emission
+#[derive(Message, Copy, Clone, Debug)]
+pub struct BearingMessage(pub Entity, pub AbsRot, pub Emission);
+
+pub(crate) fn apply_jammer(mut query: Query<(&mut Jammer, &mut Power)>) {
+    for (mut jammer, mut power) in query.iter_mut() {
+        if !jammer.active {
+            jammer.jamming = false;
+            jammer.carry = 0;
+            continue;
+        }
+
+        let (step, carry) = tick_step(jammer.power_cost, jammer.carry, TICK_HZ);
+        jammer.carry = carry;
+        jammer.jamming = power.draw(step);
+    }
+}
+
+fn apply_decoy_launch(
+    mut commands: Commands,
+    ticks: Res<Ticks>,
+    mut query: Query<(&mut DecoyLauncher, &Faction, &Position, &LinearVelocity)>,
+) {
+    for (mut launcher, faction, pos, velocity) in query.iter_mut() {
+        let Some(launch) = launcher.launch.take() else {
+            continue;
+        };
+        if launcher.remaining == 0 {
+            continue;
+        }
+        launcher.remaining -= 1;
+
+        commands.spawn((
+            Decoy {
+                expires: ticks.now() + DECOY_LIFETIME,
+            },
+            *faction,
+            RigidBody::Kinematic,
+            Position(pos.0),
+            LinearVelocity(velocity.0 + launch.as_vec2()),
+            Transform::from_translation(pos.0.extend(0.)),
+        ));
+    }
+}
+
+fn apply_decoy(mut commands: Commands, ticks: Res<Ticks>, query: Query<(Entity, &Decoy)>) {
+    for (entity, decoy) in query.iter() {
+        if ticks.now() >= decoy.expires {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Collect all of the emitters on the field, then each ship listens for the enemy ones
+#[expect(clippy::type_complexity)]
+fn apply_passive_sensor(
+    mut message: MessageWriter<BearingMessage>,
+    ship_query: Query<
+        (
+            Entity,
+            &Position,
+            &Faction,
+            Option<&Thrust>,
+            Option<&Jammer>,
+            Has<Sleep>,
+        ),
+        With<Ship>,
+    >,
+    radar_query: Query<(&AttachedTo, &RadarRange), With<Radar>>,
+    decoy_query: Query<(&Position, &Faction), With<Decoy>>,
+) {
+    let mut emitters: Vec<(IVec2, Faction, Emission, i64)> = vec![];
+
+    for (_, pos, faction, thrust, jammer, _) in ship_query.iter().filter(|ship| !ship.5) {
+        let pos = pos.0.as_ivec2();
+        if thrust.is_some_and(|thrust| thrust.acceleration != 0) {
+            emitters.push((pos, *faction, Emission::Engine, ENGINE_EMISSION));
+        }
+        if jammer.is_some_and(|jammer| jammer.jamming) {
+            emitters.push((pos, *faction, Emission::Jammer, JAMMER_EMISSION));
+        }
+    }
+    // The longer the radar reach the louder it is
+    for (attached_to, range) in radar_query.iter() {
+        if let Ok((_, pos, faction, _, _, false)) = ship_query.get(attached_to.0) {
+            let emission = range.0 * RADAR_EMISSION_FACTOR;
+            emitters.push((pos.0.as_ivec2(), *faction, Emission::Radar, emission));
+        }
+    }
+    for (pos, faction) in decoy_query.iter() {
+        emitters.push((pos.0.as_ivec2(), *faction, Emission::Radar, RADAR_EMISSION));
+    }
+
+    // Sensor is off while the ship sleeps
+    for (entity, pos, faction, _, _, _) in ship_query.iter().filter(|ship| !ship.5) {
+        let base = pos.0.as_ivec2();
+        for (emitter, emitter_faction, emission, range) in &emitters {
+            if emitter_faction == faction
+                || base.as_i64vec2().distance_squared(emitter.as_i64vec2()) > range.pow(2)
+            {
+                continue;
+            }
+            if let Some(bearing) = AbsRot::from_vec2_angle(base, *emitter) {
+                message.write(BearingMessage(entity, bearing, *emission));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_jammer_covers() {
+    let jammer = Jammer::default();
+    let base = IVec2::ZERO;
+
+    // Dead ahead, within range
+    assert!(jammer.covers(base, AbsRot(0), IVec2::new(0, 1000)));
+
+    // Too far
+    assert!(!jammer.covers(base, AbsRot(0), IVec2::new(0, 5000)));
+
+    // Behind the jammer
+    assert!(!jammer.covers(base, AbsRot(0), IVec2::new(0, -1000)));
+
+    // Facing east
+    assert!(jammer.covers(base, AbsRot(64), IVec2::new(1000, 0)));
+}
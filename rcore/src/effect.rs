@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::time::Ticks;
+
+// Visual effect subsystem:
+// - The weapon subsystems spawn an `Effect` entity for anything worth drawing (beams, blasts,
+//   shield impacts). It's plain data, the render (or an exporter) decides what it looks like.
+// - Effects live for a number of sim ticks and get despawned once they run out, so they pause and
+//   step along with the sim and a headless run doesn't pile them up.
+pub struct EffectPlugin;
+impl Plugin for EffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            apply_effect_expiry.in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+// Lifetimes in ticks
+pub const BEAM_TICKS: u64 = 48;
+pub const BLAST_TICKS: u64 = 64;
+pub const SHIELD_IMPACT_TICKS: u64 = 24;
+
+#[derive(Debug, Clone, Copy)]
+pub enum EffectKind {
+    // Weapon beam from the shooter to the target
+    Beam { origin: Vec2, target: Vec2 },
+    // Warhead or reactor going off, grows out to the radius
+    Blast { origin: Vec2, radius: i64 },
+    // Hit soaked up by the shield, flashes the shield arc
+    ShieldImpact { shield: Entity, pos: Vec2 },
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Effect {
+    pub kind: EffectKind,
+    // Tick it was spawned on
+    pub spawned: u64,
+    pub lifetime: u64,
+}
+
+impl Effect {
+    pub fn beam(ticks: &Ticks, origin: Vec2, target: Vec2) -> Self {
+        Self::new(ticks, EffectKind::Beam { origin, target }, BEAM_TICKS)
+    }
+
+    pub fn blast(ticks: &Ticks, origin: Vec2, radius: i64) -> Self {
+        Self::new(ticks, EffectKind::Blast { origin, radius }, BLAST_TICKS)
+    }
+
+    pub fn shield_impact(ticks: &Ticks, shield: Entity, pos: Vec2) -> Self {
+        Self::new(
+            ticks,
+            EffectKind::ShieldImpact { shield, pos },
+            SHIELD_IMPACT_TICKS,
+        )
+    }
+
+    fn new(ticks: &Ticks, kind: EffectKind, lifetime: u64) -> Self {
+        Self {
+            kind,
+            spawned: ticks.now(),
+            lifetime,
+        }
+    }
+
+    // How far along the effect is, 0.0 when spawned to 1.0 when it runs out
+    pub fn progress(&self, ticks: &Ticks) -> f32 {
+        let elapsed = ticks.elapsed(self.spawned).min(self.lifetime);
+        (elapsed as f32 / self.lifetime.max(1) as f32).clamp(0.0, 1.0)
+    }
+}
+
+fn apply_effect_expiry(mut commands: Commands, ticks: Res<Ticks>, query: Query<(Entity, &Effect)>) {
+    for (entity, effect) in query.iter() {
+        if ticks.is_ready(effect.spawned, effect.lifetime) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use std::collections::VecDeque;
+
+use crate::FixedGameSystem;
+use crate::math::FP_SCALE;
+use crate::mining::Cargo;
+use crate::mining::apply_faction_resources;
+use crate::mining::apply_mining;
+use crate::movement::apply_thrust;
+use crate::power::Power;
+use crate::rotation::Heading;
+use crate::script::Script;
+use crate::script::ShipScript;
+use crate::ship::Faction;
+use crate::ship::Loadout;
+use crate::ship::Ship;
+use crate::ship::ShipBuilder;
+use crate::ship::ShipClass;
+use crate::spawner::SpawnMessage;
+use crate::weapon::apply_shield_regen;
+
+// Ship fabrication subsystem:
+// - A ship with a fabrication bay can build ships of the same class or smaller than itself.
+// - Scripts queue up build orders (class + loadout + script for the new ship), the bay works
+//   through the queue one order at a time.
+// - Each order takes the class build ticks and draws its share of the build power and resources
+//   (from the cargo hold) every tick, if there isn't enough of either the build stalls for that
+//   tick.
+// - Finished ships gets launched behind the parent and the parent gets notified.
+pub struct FabricationPlugin;
+impl Plugin for FabricationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<BuildCompleteMessage>().add_systems(
+            FixedUpdate,
+            // Shares the power bank with the shield and the cargo hold with the mining, and
+            // launches at the parent velocity for this tick
+            apply_fabrication
+                .after(apply_thrust)
+                .after(apply_shield_regen)
+                .after(apply_mining)
+                .before(apply_faction_resources)
+                .in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+// How many orders the bay can hold, including the one being worked on
+pub const MAX_QUEUE: usize = 4;
+
+// Gap between the parent hull and the freshly built ship
+const LAUNCH_GAP: i64 = 50;
+
+#[derive(Debug, Clone)]
+pub struct BuildOrder {
+    pub class: ShipClass,
+    pub loadout: Loadout,
+    pub script: Script,
+}
+
+impl BuildOrder {
+    pub fn new(class: ShipClass, loadout: Loadout, script: Box<dyn ShipScript>) -> Self {
+        Self {
+            class,
+            loadout,
+            script: Script { script },
+        }
+    }
+}
+
+#[derive(Component, Clone, Default)]
+pub struct FabricationBay {
+    pub queue: VecDeque<BuildOrder>,
+    // Ticks spent on the order at the front of the queue
+    pub progress: u32,
+}
+
+impl FabricationBay {
+    // Returns if the order got queued
+    pub fn queue(&mut self, builder: ShipClass, order: BuildOrder) -> bool {
+        if !builder.can_build(order.class) || self.queue.len() >= MAX_QUEUE {
+            return false;
+        }
+        self.queue.push_back(order);
+        true
+    }
+}
+
+// Power or resources to draw for the given tick of the build, spreads the total over the build
+// without losing any to rounding
+pub fn build_step(total: u32, ticks: u32, progress: u32) -> u32 {
+    let ticks = u64::from(ticks.max(1));
+    let spent = |tick: u32| u64::from(total) * u64::from(tick).min(ticks) / ticks;
+    u32::try_from(spent(progress + 1) - spent(progress)).unwrap_or(u32::MAX)
+}
+
+// 0 - parent ship
+// 1 - class of the ship that got launched
+#[derive(Message, Copy, Clone, Debug)]
+pub struct BuildCompleteMessage(pub Entity, pub ShipClass);
+
+#[expect(clippy::type_complexity)]
+pub(crate) fn apply_fabrication(
+    mut query: Query<(
+        Entity,
+        &mut FabricationBay,
+        &mut Power,
+        &mut Cargo,
+        &Ship,
+        &Faction,
+        &Position,
+        &Heading,
+        &LinearVelocity,
+    )>,
+    mut spawn_ship: MessageWriter<SpawnMessage>,
+    mut complete: MessageWriter<BuildCompleteMessage>,
+) {
+    for (entity, mut bay, mut power, mut cargo, ship, faction, pos, heading, velocity) in
+        query.iter_mut()
+    {
+        let Some(order) = bay.queue.front() else {
+            continue;
+        };
+        let stats = order.class.stats();
+
+        // Stall till there's enough power and resources for this tick
+        let resources = build_step(stats.build_resources, stats.build_ticks, bay.progress);
+        if cargo.current < resources
+            || !power.draw(build_step(
+                stats.build_power,
+                stats.build_ticks,
+                bay.progress,
+            ))
+        {
+            continue;
+        }
+        cargo.current -= resources;
+
+        bay.progress += 1;
+        if bay.progress < stats.build_ticks {
+            continue;
+        }
+
+        let order = bay.queue.pop_front().expect("order");
+        bay.progress = 0;
+
+        // Launch it behind the parent so that the hulls don't overlap
+        #[expect(clippy::cast_possible_truncation)]
+        let distance = (ship.0.stats().collider_radius + stats.collider_radius) as i64 + LAUNCH_GAP;
+        let offset = pos.0.as_ivec2()
+            - (heading.0.to_heading_fp().as_i64vec2() * distance / FP_SCALE).as_ivec2();
+        let velocity = velocity.0.as_ivec2();
+
+        let new_ship = ShipBuilder::new(order.script)
+            .class(order.class)
+            .faction(faction.0)
+            .loadout(order.loadout)
+            .position(offset.x, offset.y)
+            .rotation(heading.0)
+            .velocity(velocity.x, velocity.y)
+            .build();
+
+        spawn_ship.write(SpawnMessage(new_ship));
+        complete.write(BuildCompleteMessage(entity, order.class));
+    }
+}
+
+#[test]
+fn test_build_step() {
+    // Spread evenly
+    assert_eq!(build_step(100, 4, 0), 25);
+    assert_eq!(build_step(100, 4, 3), 25);
+
+    // Uneven split still adds up to the total
+    let total: u32 = (0..3).map(|tick| build_step(100, 3, tick)).sum();
+    assert_eq!(total, 100);
+
+    // Nothing left once the build is done
+    assert_eq!(build_step(100, 4, 4), 0);
+}
+
+#[test]
+fn test_fabrication_queue() {
+    use crate::testing::Idle;
+
+    let order = |class| BuildOrder::new(class, Loadout::new(), Box::new(Idle));
+
+    // Nothing bigger than the builder, and only so many orders
+    let mut bay = FabricationBay::default();
+    assert!(!bay.queue(ShipClass::Small, order(ShipClass::Medium)));
+    for _ in 0..MAX_QUEUE {
+        assert!(bay.queue(ShipClass::Small, order(ShipClass::Tiny)));
+    }
+    assert!(!bay.queue(ShipClass::Small, order(ShipClass::Tiny)));
+    assert_eq!(bay.queue.len(), MAX_QUEUE);
+}
+
+#[test]
+fn test_apply_fabrication() {
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    use crate::TICK_HZ;
+    use crate::math::AbsRot;
+    use crate::testing::Idle;
+    use crate::time::TimeControlPlugin;
+
+    let order = |class| BuildOrder::new(class, Loadout::new(), Box::new(Idle));
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TimeControlPlugin))
+        .add_message::<SpawnMessage>()
+        .add_message::<BuildCompleteMessage>()
+        .add_systems(FixedUpdate, apply_fabrication)
+        .insert_resource(Time::<Fixed>::from_hz(f64::from(TICK_HZ)))
+        // One tick per update
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / f64::from(TICK_HZ),
+        )));
+
+    let mut builder = |power: u32, cargo: u32| {
+        let mut bay = FabricationBay::default();
+        assert!(bay.queue(ShipClass::Small, order(ShipClass::Tiny)));
+        app.world_mut()
+            .spawn((
+                bay,
+                Power::new(power, 0),
+                Cargo {
+                    current: cargo,
+                    capacity: 1000,
+                },
+                Ship(ShipClass::Small),
+                Faction(0),
+                Position(Vec2::ZERO),
+                Heading(AbsRot(0)),
+                LinearVelocity(Vec2::ZERO),
+            ))
+            .id()
+    };
+    let no_cargo = builder(1000, 0);
+    let no_power = builder(0, 1000);
+    let ready = builder(1000, 1000);
+
+    let mut spawn_cursor = app
+        .world()
+        .resource::<Messages<SpawnMessage>>()
+        .get_cursor();
+    let mut complete_cursor = app
+        .world()
+        .resource::<Messages<BuildCompleteMessage>>()
+        .get_cursor();
+    let mut spawned = 0;
+    let mut completed = vec![];
+    let mut step = |app: &mut App| {
+        app.update();
+        let world = app.world();
+        spawned += spawn_cursor
+            .read(world.resource::<Messages<SpawnMessage>>())
+            .count();
+        completed.extend(
+            complete_cursor
+                .read(world.resource::<Messages<BuildCompleteMessage>>())
+                .map(|message| (message.0, message.1)),
+        );
+    };
+    let progress = |app: &App, ship| {
+        app.world()
+            .get::<FabricationBay>(ship)
+            .expect("bay")
+            .progress
+    };
+
+    // Both stall as soon as the tick needs what they don't have
+    for _ in 0..20 {
+        step(&mut app);
+    }
+    let stalled = [progress(&app, no_cargo), progress(&app, no_power)];
+    for _ in 0..20 {
+        step(&mut app);
+    }
+    assert_eq!(
+        stalled,
+        [progress(&app, no_cargo), progress(&app, no_power)]
+    );
+    assert!(stalled.iter().all(|progress| *progress < 20));
+
+    // Bay goes back to 0 once the build is done
+    while progress(&app, ready) != 0 {
+        step(&mut app);
+    }
+    assert_eq!(completed, [(ready, ShipClass::Tiny)]);
+    assert_eq!(spawned, 1);
+
+    // Whole build cost got drawn, and the bay is ready for the next order
+    let stats = ShipClass::Tiny.stats();
+    let world = app.world();
+    assert_eq!(
+        world.get::<Cargo>(ready).expect("cargo").current,
+        1000 - stats.build_resources
+    );
+    assert_eq!(
+        world.get::<Power>(ready).expect("power").current,
+        1000 - stats.build_power
+    );
+    assert!(
+        world
+            .get::<FabricationBay>(ready)
+            .expect("bay")
+            .queue
+            .is_empty()
+    );
+}
@@ -0,0 +1,231 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::destruction::Wreck;
+use crate::movement::apply_thrust;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::stats::SimEvent;
+use crate::stats::SimEventKind;
+use crate::time::Ticks;
+use crate::weapon::DamageCause;
+use crate::weapon::DamageEvent;
+use crate::weapon::DebugWarhead;
+use crate::weapon::FireDebugWarheadMessage;
+
+// Ship impact subsystem:
+// - Hulls, missiles (tiny craft) and shields each sit on their own collision layer, and each
+//   faction gets a layer of its own.
+// - Hulls can ram anyone, missiles and shields only interact with the other factions, so you can't
+//   blow up your own ship with your own missile.
+// - Hull on hull impacts deal kinetic damage, the relative velocity gets split between the two
+//   ships by their mass, the lighter ship takes the brunt of it.
+// - Anything carrying a warhead detonates upon contact instead.
+pub struct ImpactPlugin;
+impl Plugin for ImpactPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ImpactMessage>().add_systems(
+            FixedUpdate,
+            // Impact velocity is from the physics step that generated the collision
+            process_impact
+                .before(apply_thrust)
+                .in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+// Bits 0-7 are the kind of collider, bits 8-31 are the factions
+const HULL_LAYER: u32 = 1 << 0;
+const MISSILE_LAYER: u32 = 1 << 1;
+const SHIELD_LAYER: u32 = 1 << 2;
+
+const FACTION_SHIFT: u32 = 8;
+const FACTION_LAYERS: u32 = u32::MAX << FACTION_SHIFT;
+pub const MAX_FACTIONS: u8 = 24;
+
+// Any impact below this relative velocity is a bump and does no damage
+pub const IMPACT_THRESHOLD: u32 = 10;
+
+// Velocity change a hull can soak per point of damage
+pub const IMPACT_FACTOR: u32 = 2;
+
+fn faction_layer(faction: Faction) -> u32 {
+    1 << (FACTION_SHIFT + u32::from(faction.0))
+}
+
+fn enemy_layers(faction: Faction) -> u32 {
+    FACTION_LAYERS & !faction_layer(faction)
+}
+
+pub fn ship_layers(class: ShipClass, faction: Faction) -> CollisionLayers {
+    match class {
+        // Missiles and other tiny craft slips past friendly hulls and shields
+        ShipClass::Tiny => CollisionLayers::new(
+            MISSILE_LAYER | faction_layer(faction),
+            enemy_layers(faction),
+        ),
+        ShipClass::Large | ShipClass::Medium | ShipClass::Small => CollisionLayers::new(
+            HULL_LAYER | faction_layer(faction),
+            HULL_LAYER | MISSILE_LAYER,
+        ),
+    }
+}
+
+// Shield only cares about enemy missiles, the missile layers takes care of the faction check
+pub fn shield_layers(faction: Faction) -> CollisionLayers {
+    CollisionLayers::new(SHIELD_LAYER | faction_layer(faction), MISSILE_LAYER)
+}
+
+// Wrecks are hulls without a faction, anything but missiles can still run into them
+pub fn wreck_layers() -> CollisionLayers {
+    CollisionLayers::new(HULL_LAYER, HULL_LAYER)
+}
+
+pub(crate) fn hull_class(hull: (Option<&Ship>, Option<&Wreck>)) -> ShipClass {
+    match hull {
+        (Some(ship), _) => ship.0,
+        (None, Some(wreck)) => wreck.0,
+        (None, None) => unreachable!(),
+    }
+}
+
+// Hull damage from an impact, each ship sees the share of the relative velocity that the other
+// ship mass imparts onto it
+pub fn impact_damage(relative_speed: u32, mass: u32, other_mass: u32) -> u16 {
+    if relative_speed < IMPACT_THRESHOLD {
+        return 0;
+    }
+
+    let delta_v =
+        u64::from(relative_speed) * u64::from(other_mass) / u64::from(mass + other_mass).max(1);
+    u16::try_from(delta_v / u64::from(IMPACT_FACTOR)).unwrap_or(u16::MAX)
+}
+
+// 0 - self
+// 1 - what got hit
+// 2 - relative speed of the impact
+// 3 - hull damage self took
+#[derive(Message, Copy, Clone, Debug)]
+pub struct ImpactMessage(pub Entity, pub Entity, pub u32, pub u16);
+
+#[expect(clippy::type_complexity)]
+pub(crate) fn process_impact(
+    mut commands: Commands,
+    mut collision_events: MessageReader<CollisionStart>,
+    mut impact: MessageWriter<ImpactMessage>,
+    mut warhead: MessageWriter<FireDebugWarheadMessage>,
+    query: Query<(
+        AnyOf<(&Ship, &Wreck)>,
+        &Position,
+        &LinearVelocity,
+        Has<DebugWarhead>,
+    )>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
+) {
+    for event in collision_events.read() {
+        // Shields are handled by the weapon system, and the ship may already be gone
+        let Ok([(hull1, pos1, vel1, armed1), (hull2, pos2, vel2, armed2)]) =
+            query.get_many([event.collider1, event.collider2])
+        else {
+            continue;
+        };
+        let (class1, class2) = (hull_class(hull1), hull_class(hull2));
+
+        let relative_velocity = (vel1.0.as_ivec2() - vel2.0.as_ivec2()).as_i64vec2();
+        let relative_speed =
+            u32::try_from(relative_velocity.length_squared().cast_unsigned().isqrt())
+                .unwrap_or(u32::MAX);
+
+        for (ship, target, armed, class, other_class, other_pos) in [
+            (
+                event.collider1,
+                event.collider2,
+                armed1,
+                class1,
+                class2,
+                pos2,
+            ),
+            (
+                event.collider2,
+                event.collider1,
+                armed2,
+                class2,
+                class1,
+                pos1,
+            ),
+        ] {
+            // Warheads goes off on contact, the blast deals the damage instead
+            let dmg = if armed1 || armed2 {
+                0
+            } else {
+                impact_damage(relative_speed, class.stats().mass, other_class.stats().mass)
+            };
+
+            if armed {
+                warhead.write(FireDebugWarheadMessage(ship));
+            }
+            if dmg > 0 {
+                commands.trigger(DamageEvent {
+                    target: ship,
+                    pos: other_pos.0.as_ivec2(),
+                    dmg,
+                    source: Some(target),
+                    cause: DamageCause::Impact,
+                });
+            }
+            impact.write(ImpactMessage(ship, target, relative_speed, dmg));
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Collision,
+                Some(ship),
+                Some(target),
+                u32::from(dmg),
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_impact_damage() {
+    // Same mass, the velocity is split evenly
+    assert_eq!(impact_damage(200, 100, 100), 50);
+
+    // Heavier ship barely notices, lighter ship takes it all
+    assert_eq!(impact_damage(200, 900, 100), 10);
+    assert_eq!(impact_damage(200, 100, 900), 90);
+
+    // Bumps are free
+    assert_eq!(impact_damage(IMPACT_THRESHOLD - 1, 100, 100), 0);
+}
+
+#[test]
+fn test_collision_layers() {
+    let (friend, enemy) = (Faction(0), Faction(1));
+
+    let hull = ship_layers(ShipClass::Medium, friend);
+    let enemy_hull = ship_layers(ShipClass::Large, enemy);
+    let missile = ship_layers(ShipClass::Tiny, friend);
+    let enemy_missile = ship_layers(ShipClass::Tiny, enemy);
+
+    // Ramming works on everyone
+    assert!(hull.interacts_with(enemy_hull));
+    assert!(hull.interacts_with(ship_layers(ShipClass::Small, friend)));
+
+    // Missiles only hits the other factions
+    assert!(!missile.interacts_with(hull));
+    assert!(missile.interacts_with(enemy_hull));
+    assert!(missile.interacts_with(enemy_missile));
+
+    // Shields only intercepts enemy missiles
+    assert!(!shield_layers(friend).interacts_with(missile));
+    assert!(shield_layers(friend).interacts_with(enemy_missile));
+    assert!(!shield_layers(friend).interacts_with(enemy_hull));
+
+    // Wrecks are only a hazard to hulls
+    assert!(wreck_layers().interacts_with(hull));
+    assert!(wreck_layers().interacts_with(enemy_hull));
+    assert!(!wreck_layers().interacts_with(missile));
+}
@@ -2,33 +2,65 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 
 pub mod attach;
+pub mod autopilot;
+pub mod destruction;
+pub mod ecm;
+pub mod effect;
+pub mod fabrication;
+pub mod impact;
 pub mod math;
+pub mod mining;
+pub mod module;
 pub mod movement;
+pub mod nav;
+pub mod power;
 pub mod radar;
 pub mod rotation;
 pub mod script;
 pub mod ship;
+pub mod sleep;
+pub mod snapshot;
 pub mod spawner;
+pub mod stats;
+pub mod style;
 pub mod weapon;
 pub mod time;
 
 #[cfg(feature = "render")]
 pub mod render;
 
+#[cfg(test)]
+mod testing;
+
 use crate::math::AbsRot;
 
 use crate::attach::AttachPlugin;
+use crate::autopilot::AutopilotPlugin;
+use crate::destruction::DestructionPlugin;
+use crate::ecm::EcmPlugin;
+use crate::effect::EffectPlugin;
+use crate::fabrication::FabricationPlugin;
+use crate::impact::ImpactPlugin;
+use crate::mining::MiningPlugin;
+use crate::module::ModulePlugin;
 use crate::movement::MovementPlugin;
+use crate::power::PowerPlugin;
 use crate::radar::RadarPlugin;
 use crate::rotation::RotationPlugin;
 use crate::script::ScriptPlugins;
+use crate::sleep::SleepPlugin;
+use crate::snapshot::SnapshotPlugin;
 use crate::spawner::SpawnerPlugin;
+use crate::stats::StatsPlugin;
 use crate::time::TimeControlPlugin;
 use crate::weapon::WeaponPlugin;
 
 // Sim timing
 pub const TICK_HZ: u32 = 64;
 
+// Size of the arena bounds (world units), centered on the origin
+pub const ARENA_SIZE: Vec2 = Vec2::new(10240., 6400.);
+
 // Systemset to help group systems in a defined order of operation since we now have systems that
 // depends on previous systems, and this will help avoid the 1+ frame delay when using events
 //
@@ -67,11 +99,23 @@ impl Plugin for SimulationPlugin {
             .add_plugins(TimeControlPlugin)
             // Game bits
             .add_plugins(AttachPlugin)
+            .add_plugins(AutopilotPlugin)
+            .add_plugins(DestructionPlugin)
+            .add_plugins(EcmPlugin)
+            .add_plugins(EffectPlugin)
+            .add_plugins(FabricationPlugin)
+            .add_plugins(ImpactPlugin)
+            .add_plugins(MiningPlugin)
+            .add_plugins(ModulePlugin)
             .add_plugins(MovementPlugin)
+            .add_plugins(PowerPlugin)
             .add_plugins(RadarPlugin)
             .add_plugins(RotationPlugin)
             .add_plugins(ScriptPlugins)
+            .add_plugins(SleepPlugin)
+            .add_plugins(SnapshotPlugin)
             .add_plugins(SpawnerPlugin)
+            .add_plugins(StatsPlugin)
             .add_plugins(WeaponPlugin)
             // System set ordering
             .configure_sets(
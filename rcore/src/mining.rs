@@ -0,0 +1,303 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use std::collections::BTreeMap;
+
+use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::math::tick_step;
+use crate::movement::apply_thrust;
+use crate::ship::Faction;
+use crate::ship::Ship;
+
+// Mining and cargo subsystem:
+// - The scenario places resource fields (asteroids) around the arena, each with a finite amount of
+//   resources, depleted fields are despawned.
+// - Ships with a mining module pulls resources from the closest field when within range and
+//   nearly stopped relative to it, into their cargo hold.
+// - Cargo holds are limited by the ship class, cargo can be handed over to friendly ships nearby.
+// - Resource totals per faction are tracked for the scripts and for match victory conditions.
+pub struct MiningPlugin;
+impl Plugin for MiningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TransferMessage>()
+            .init_resource::<FactionResources>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    process_transfer_message,
+                    apply_mining,
+                    apply_faction_resources,
+                )
+                    .chain()
+                    .after(apply_thrust)
+                    .in_set(FixedGameSystem::GameLogic),
+            );
+    }
+}
+
+// Distance from the field the mining module can reach
+pub const MINING_RANGE: i64 = 600;
+pub const MINING_RANGE_SQUARED: i64 = MINING_RANGE.pow(2);
+
+// Ship has to be this slow relative to the field to mine it
+pub const MINING_SPEED: i64 = 10;
+
+// Distance between two ships for cargo transfers
+pub const TRANSFER_RANGE: i64 = 800;
+pub const TRANSFER_RANGE_SQUARED: i64 = TRANSFER_RANGE.pow(2);
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ResourceField {
+    pub amount: u32,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Mining {
+    // Resources per second
+    pub rate: u32,
+    // Sub tick mining
+    pub carry: u32,
+}
+
+impl Default for Mining {
+    fn default() -> Self {
+        Self { rate: 20, carry: 0 }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Cargo {
+    pub current: u32,
+    pub capacity: u32,
+}
+
+impl Cargo {
+    pub fn space(&self) -> u32 {
+        self.capacity - self.current
+    }
+}
+
+// Resources per faction
+// - held, sum of the cargo across the faction ships
+// - mined, lifetime total mined by the faction
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FactionResources {
+    pub held: BTreeMap<Faction, u32>,
+    pub mined: BTreeMap<Faction, u64>,
+}
+
+impl FactionResources {
+    pub fn held(&self, faction: Faction) -> u32 {
+        self.held.get(&faction).copied().unwrap_or(0)
+    }
+
+    pub fn mined(&self, faction: Faction) -> u64 {
+        self.mined.get(&faction).copied().unwrap_or(0)
+    }
+}
+
+// 0 - self
+// 1 - ship to hand the cargo over to
+// 2 - amount
+#[derive(Message, Copy, Clone, Debug)]
+pub struct TransferMessage(pub Entity, pub Entity, pub u32);
+
+// Scenario setup
+pub fn add_resource_field(commands: &mut Commands, x: i32, y: i32, amount: u32) -> Entity {
+    let position = IVec2::new(x, y).as_vec2();
+
+    commands
+        .spawn((
+            ResourceField { amount },
+            Position(position),
+            Transform::from_translation(position.extend(0.)),
+        ))
+        .id()
+}
+
+pub(crate) fn process_transfer_message(
+    mut transfer_message: MessageReader<TransferMessage>,
+    mut query: Query<(&mut Cargo, &Faction, &Position), With<Ship>>,
+) {
+    for TransferMessage(ship, target, amount) in transfer_message.read() {
+        // Either ship may have been destroyed since
+        let Ok(
+            [
+                (mut cargo, faction, pos),
+                (mut target_cargo, target_faction, target_pos),
+            ],
+        ) = query.get_many_mut([*ship, *target])
+        else {
+            continue;
+        };
+
+        if faction != target_faction
+            || pos
+                .0
+                .as_ivec2()
+                .as_i64vec2()
+                .distance_squared(target_pos.0.as_ivec2().as_i64vec2())
+                > TRANSFER_RANGE_SQUARED
+        {
+            continue;
+        }
+
+        let amount = (*amount).min(cargo.current).min(target_cargo.space());
+        cargo.current -= amount;
+        target_cargo.current += amount;
+    }
+}
+
+pub(crate) fn apply_mining(
+    mut commands: Commands,
+    mut resources: ResMut<FactionResources>,
+    mut query: Query<
+        (
+            &mut Mining,
+            &mut Cargo,
+            &Faction,
+            &Position,
+            &LinearVelocity,
+        ),
+        With<Ship>,
+    >,
+    mut field_query: Query<(Entity, &mut ResourceField, &Position)>,
+) {
+    for (mut mining, mut cargo, faction, pos, velocity) in query.iter_mut() {
+        let base = pos.0.as_ivec2().as_i64vec2();
+
+        // Need to hold still to mine, and somewhere to put it
+        if velocity.0.as_ivec2().as_i64vec2().length_squared() > MINING_SPEED.pow(2)
+            || cargo.space() == 0
+        {
+            continue;
+        }
+
+        // Closest field with something left, tiebreak by entity to preserve replay orders
+        let Some((field_entity, _)) = field_query
+            .iter()
+            .filter(|(_, field, _)| field.amount > 0)
+            .map(|(entity, _, field_pos)| {
+                (
+                    entity,
+                    base.distance_squared(field_pos.0.as_ivec2().as_i64vec2()),
+                )
+            })
+            .filter(|(_, distance)| *distance <= MINING_RANGE_SQUARED)
+            .min_by_key(|(entity, distance)| (*distance, *entity))
+        else {
+            continue;
+        };
+
+        let (step, carry) = tick_step(mining.rate, mining.carry, TICK_HZ);
+        mining.carry = carry;
+
+        let (_, mut field, _) = field_query.get_mut(field_entity).expect("field");
+        let amount = step.min(field.amount).min(cargo.space());
+        field.amount -= amount;
+        cargo.current += amount;
+        *resources.mined.entry(*faction).or_default() += u64::from(amount);
+
+        if field.amount == 0 {
+            println!("Field depleted - {field_entity:?}");
+            commands.entity(field_entity).despawn();
+        }
+    }
+}
+
+pub(crate) fn apply_faction_resources(
+    mut resources: ResMut<FactionResources>,
+    query: Query<(&Cargo, &Faction), With<Ship>>,
+) {
+    resources.held.clear();
+    for (cargo, faction) in query.iter() {
+        *resources.held.entry(*faction).or_default() += cargo.current;
+    }
+}
+
+#[test]
+fn test_transfer() {
+    use crate::ship::ShipClass;
+
+    let mut app = App::new();
+    app.add_message::<TransferMessage>()
+        .add_systems(Update, process_transfer_message);
+
+    let mut ship = |current: u32, faction: u8, x: f32| {
+        app.world_mut()
+            .spawn((
+                Ship(ShipClass::Medium),
+                Cargo {
+                    current,
+                    capacity: 200,
+                },
+                Faction(faction),
+                Position(Vec2::new(x, 0.)),
+            ))
+            .id()
+    };
+    let base = ship(100, 0, 0.);
+    let friend = ship(150, 0, 500.);
+    let far = ship(0, 0, 5000.);
+    let enemy = ship(0, 1, 100.);
+
+    // Out of range and other faction gets nothing, the friend only has room for 50
+    for (target, amount) in [(far, 10), (enemy, 10), (friend, 80)] {
+        app.world_mut()
+            .write_message(TransferMessage(base, target, amount));
+    }
+    app.update();
+
+    let cargo = |entity| app.world().get::<Cargo>(entity).expect("cargo").current;
+    assert_eq!(cargo(base), 50);
+    assert_eq!(cargo(friend), 200);
+    assert_eq!(cargo(far), 0);
+    assert_eq!(cargo(enemy), 0);
+}
+
+#[test]
+fn test_apply_mining() {
+    use crate::ship::ShipClass;
+
+    let mut app = App::new();
+    app.init_resource::<FactionResources>()
+        .add_systems(Update, apply_mining);
+
+    let field = add_resource_field(&mut app.world_mut().commands(), 0, 0, 1000);
+    let ship = app
+        .world_mut()
+        .spawn((
+            Ship(ShipClass::Medium),
+            Mining::default(),
+            Cargo {
+                current: 195,
+                capacity: 200,
+            },
+            Faction(0),
+            Position(Vec2::new(100., 0.)),
+            LinearVelocity::ZERO,
+        ))
+        .id();
+
+    // A second worth of mining, clamped to what fits in the hold
+    for _ in 0..TICK_HZ {
+        app.update();
+    }
+    assert_eq!(app.world().get::<Cargo>(ship).expect("cargo").current, 200);
+    assert_eq!(
+        app.world()
+            .get::<ResourceField>(field)
+            .expect("field")
+            .amount,
+        995
+    );
+
+    // Full hold, the mining is on hold too
+    let carry = app.world().get::<Mining>(ship).expect("mining").carry;
+    app.update();
+    assert_eq!(
+        app.world().get::<Mining>(ship).expect("mining").carry,
+        carry
+    );
+}
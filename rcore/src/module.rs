@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+
+use crate::attach::AttachedTo;
+use crate::ecm::Jammer;
+use crate::fabrication::FabricationBay;
+use crate::math::AbsRot;
+use crate::math::RelRot;
+use crate::mining::Mining;
+use crate::movement::Thrust;
+use crate::weapon::DebugMissile;
+use crate::weapon::DebugWeapon;
+
+// Ship module subsystem:
+// - Each piece of hardware (radar, shield generator, turret, engine, fabricator, mining laser,
+//   jammer) is an attachment on the ship with its own structural health.
+// - Damage that gets past the shield lands on one side of the ship, the modules mounted on that
+//   side split the hit between them alongside the hull.
+// - Destroyed modules are despawned, for hardware that lives on the ship itself (engine, turret,
+//   fabricator, mining laser, jammer) the relevant component is also stripped off the ship.
+pub struct ModulePlugin;
+impl Plugin for ModulePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(process_module_damage_event);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    Radar,
+    Shield,
+    Weapon,
+    Engine,
+    Fabricator,
+    Mining,
+    Jammer,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Module {
+    pub kind: ModuleKind,
+    // Mount direction, relative to the ship heading
+    pub mount: RelRot,
+    // Half-arc around the mount direction of hits that can reach this module
+    pub exposure: u8,
+    pub current: u16,
+    pub maximum: u16,
+}
+
+impl Module {
+    // TODO: these are placeholder values till we have a way to customize the loadout
+    pub fn new(kind: ModuleKind) -> Self {
+        let (mount, exposure, health) = match kind {
+            // Forward facing hardware
+            ModuleKind::Radar => (RelRot(0), 32, 40),
+            ModuleKind::Weapon => (RelRot(0), 48, 60),
+            // Aft hardware
+            ModuleKind::Shield => (RelRot(-128), 48, 60),
+            ModuleKind::Engine => (RelRot(-128), 32, 60),
+            // Launch bay sits on the starboard side
+            ModuleKind::Fabricator => (RelRot(64), 48, 80),
+            // Mining laser up front to reach the field
+            ModuleKind::Mining => (RelRot(0), 32, 40),
+            // Jammer points where the ship points
+            ModuleKind::Jammer => (RelRot(0), 32, 40),
+        };
+
+        Self {
+            kind,
+            mount,
+            exposure,
+            current: health,
+            maximum: health,
+        }
+    }
+
+    pub fn health(&mut self, health: u16) {
+        self.current = health;
+        self.maximum = health;
+    }
+
+    // Is the module on the side of the ship that got hit?
+    pub fn exposed(&self, ship_heading: AbsRot, hit: AbsRot) -> bool {
+        (ship_heading + self.mount).within(self.exposure, hit)
+    }
+}
+
+// Splits the hit evenly across the exposed modules, the first ones take the remainder so none of
+// the damage gets lost
+pub fn split_damage(dmg: u16, modules: usize) -> impl Iterator<Item = u16> {
+    let count = u16::try_from(modules).unwrap_or(u16::MAX).max(1);
+    let (share, remainder) = (dmg / count, dmg % count);
+    (0..count)
+        .take(modules)
+        .map(move |idx| share + u16::from(idx < remainder))
+}
+
+// 0 - Module entity
+// 1 - Structural damage to apply
+#[derive(EntityEvent, Copy, Clone, Debug)]
+pub struct ModuleDamageEvent {
+    #[event_target]
+    pub target: Entity,
+    pub dmg: u16,
+}
+
+#[expect(clippy::needless_pass_by_value)]
+fn process_module_damage_event(
+    trigger: On<ModuleDamageEvent>,
+    mut commands: Commands,
+    mut query: Query<(&mut Module, &AttachedTo)>,
+) {
+    let target = trigger.event().target;
+    let Ok((mut module, attached_to)) = query.get_mut(target) else {
+        return;
+    };
+
+    // Already destroyed earlier this tick, waiting on the despawn
+    if module.current == 0 {
+        return;
+    }
+
+    module.current = module.current.saturating_sub(trigger.event().dmg);
+    if module.current == 0 {
+        println!("Module destroyed - {:?} - {target:?}", module.kind);
+
+        // Strip the hardware off the ship, the ship itself may be dying this tick as well
+        match module.kind {
+            ModuleKind::Engine => {
+                commands.entity(attached_to.0).try_remove::<Thrust>();
+            }
+            ModuleKind::Weapon => {
+                commands
+                    .entity(attached_to.0)
+                    .try_remove::<(DebugWeapon, DebugMissile)>();
+            }
+            // Anything in the bay is lost with it
+            ModuleKind::Fabricator => {
+                commands
+                    .entity(attached_to.0)
+                    .try_remove::<FabricationBay>();
+            }
+            ModuleKind::Mining => {
+                commands.entity(attached_to.0).try_remove::<Mining>();
+            }
+            ModuleKind::Jammer => {
+                commands.entity(attached_to.0).try_remove::<Jammer>();
+            }
+            // Radar and shield are the module entity itself
+            ModuleKind::Radar | ModuleKind::Shield => {}
+        }
+
+        // Despawning the module drops it from the ship `Attachments`
+        commands.entity(target).despawn();
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_module_exposed() {
+    let radar = Module::new(ModuleKind::Radar);
+    let engine = Module::new(ModuleKind::Engine);
+
+    // Ship facing north, hit from the front
+    assert!(radar.exposed(AbsRot(0), AbsRot(0)));
+    assert!(!engine.exposed(AbsRot(0), AbsRot(0)));
+
+    // Ship facing north, hit from behind
+    assert!(!radar.exposed(AbsRot(0), AbsRot(128)));
+    assert!(engine.exposed(AbsRot(0), AbsRot(128)));
+
+    // Ship facing east, hit from the west (behind) and edge of the exposure
+    assert!(engine.exposed(AbsRot(64), AbsRot(192)));
+    assert!(engine.exposed(AbsRot(64), AbsRot(160)));
+    assert!(!engine.exposed(AbsRot(64), AbsRot(159)));
+}
+
+#[test]
+fn test_split_damage() {
+    assert_eq!(split_damage(30, 1).collect::<Vec<_>>(), [30]);
+    assert_eq!(split_damage(30, 3).collect::<Vec<_>>(), [10, 10, 10]);
+    assert_eq!(split_damage(10, 3).collect::<Vec<_>>(), [4, 3, 3]);
+    assert_eq!(split_damage(1, 3).collect::<Vec<_>>(), [1, 0, 0]);
+    assert_eq!(split_damage(10, 0).count(), 0);
+}
@@ -3,7 +3,12 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::math::AbsRot;
 use crate::math::FP_SCALE;
+use crate::math::RelRot;
+use crate::math::tick_step;
+use crate::power::Power;
 use crate::rotation::Heading;
 
 pub struct MovementPlugin;
@@ -11,7 +16,10 @@ impl Plugin for MovementPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             FixedUpdate,
-            (apply_thrust.after(crate::rotation::apply_rotation),)
+            // RCS shares the power bank with the shield
+            (apply_thrust
+                .after(crate::rotation::apply_rotation)
+                .after(crate::weapon::apply_shield_regen),)
                 .in_set(FixedGameSystem::GameLogic),
         );
     }
@@ -45,9 +53,8 @@ impl MovementBundle {
     }
 }
 
-// TODO: for now have a single accleration vector from the main engine only, but eventually
-// I want to have RCS so that there can be a small amount of lateral and backward movement
-// but you would still need the main engine for heavy acceleration.
+// Main engine, accelerates along the ship heading, RCS handles the small lateral and backward
+// movement but you still need the main engine for heavy acceleration.
 #[derive(Component, Clone, Copy)]
 #[require(Position, LinearVelocity)]
 pub struct Thrust {
@@ -57,33 +64,194 @@ pub struct Thrust {
     pub velocity_limit: u32,
 }
 
+impl Thrust {
+    // World frame acceleration for the given ship heading
+    pub fn world_acceleration(&self, heading: AbsRot) -> Vec2 {
+        heading.to_heading_fp().as_vec2() / (FP_SCALE as f32) * self.acceleration as f32
+    }
+}
+
+// Reaction control system, small thrusters for dodging and backing up without turning
+// - Commanded as a ship-local vector (+Y forward, +X starboard)
+// - Each axis is clamped to its own limit
+// - Draws power every tick it is firing, no power no RCS for that tick
+// - Feeds off the main engine, loosing the engine also takes out the RCS
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Rcs {
+    pub thrust: IVec2,
+    // Strafe (X) and forward/reverse (Y) limits
+    pub lateral_limit: i32,
+    pub axial_limit: i32,
+    // Power per second while firing
+    pub power_cost: u32,
+    // Sub tick power draw
+    pub carry: u32,
+}
+
+impl Rcs {
+    pub fn new(lateral_limit: i32, axial_limit: i32, power_cost: u32) -> Self {
+        Self {
+            thrust: IVec2::ZERO,
+            lateral_limit,
+            axial_limit,
+            power_cost,
+            carry: 0,
+        }
+    }
+
+    pub fn command(&mut self, thrust: IVec2) {
+        self.thrust = IVec2::new(
+            thrust.x.clamp(-self.lateral_limit, self.lateral_limit),
+            thrust.y.clamp(-self.axial_limit, self.axial_limit),
+        );
+    }
+
+    // World frame acceleration for the given ship heading
+    pub fn acceleration(&self, heading: AbsRot) -> Vec2 {
+        let forward = heading.to_heading_fp().as_i64vec2();
+        let starboard = (heading + RelRot(64)).to_heading_fp().as_i64vec2();
+
+        let acceleration =
+            forward * i64::from(self.thrust.y) + starboard * i64::from(self.thrust.x);
+        acceleration.as_vec2() / FP_SCALE as f32
+    }
+}
+
+// Propellant tank, every unit of acceleration (main engine and RCS) burns a unit of propellant
+// per second. Once the tank runs dry the engine and RCS stops, ships without a tank have
+// unlimited propellant.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Propellant {
+    pub current: u32,
+    pub maximum: u32,
+    // Sub tick burn
+    pub carry: u32,
+}
+
+impl Propellant {
+    pub fn new(maximum: u32) -> Self {
+        Self {
+            current: maximum,
+            maximum,
+            carry: 0,
+        }
+    }
+
+    // All or nothing, returns if there was enough propellant for this tick
+    pub fn burn(&mut self, rate: u32) -> bool {
+        // Dry tank can't even do the sub tick burns
+        if rate > 0 && self.current == 0 {
+            return false;
+        }
+
+        let (step, carry) = tick_step(rate, self.carry, TICK_HZ);
+        if let Some(current) = self.current.checked_sub(step) {
+            self.current = current;
+            self.carry = carry;
+            true
+        } else {
+            self.current = 0;
+            self.carry = 0;
+            false
+        }
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct MovDebug;
 
 // TODO: improve this to integrate in forces (ie fireing of guns for smaller ships, etc)
 #[expect(clippy::needless_pass_by_value)]
-fn apply_thrust(mut query: Query<(&mut LinearVelocity, &Heading, &Thrust)>, time: Res<Time>) {
-    for (mut velocity, heading, thrust) in query.iter_mut() {
-        let acceleration =
-            heading.0.to_heading_fp().as_vec2() / (FP_SCALE as f32) * thrust.acceleration as f32;
-
-        // Apply Lorentz factor only if it will increase the velocity,
-        // this is not realistic but permits easy deceleration for the ship
-        // Inspiration: https://stackoverflow.com/a/2891162
-        //
-        // NOTE: This will make direction change be sluggish unless the ship decelerate enough to
-        // do so. Could optionally allow for a heading change while preserving the current velocity
-        let factor = if velocity.0.dot(acceleration) >= 0.0 {
-            #[expect(clippy::cast_precision_loss)]
-            lorentz_factor(velocity.0, thrust.velocity_limit as f32)
-        } else {
-            1.0
-        };
+#[expect(clippy::type_complexity)]
+pub(crate) fn apply_thrust(
+    mut query: Query<(
+        &mut LinearVelocity,
+        &Heading,
+        &Thrust,
+        Option<&mut Rcs>,
+        Option<&mut Power>,
+        Option<&mut Propellant>,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut velocity, heading, thrust, rcs, power, propellant) in query.iter_mut() {
+        let mut acceleration = thrust.world_acceleration(heading.0);
+        let mut burn = thrust.acceleration.unsigned_abs();
+
+        if let Some(mut rcs) = rcs
+            && rcs.thrust != IVec2::ZERO
+        {
+            let (step, carry) = tick_step(rcs.power_cost, rcs.carry, TICK_HZ);
+            rcs.carry = carry;
+
+            // Unpowered ship can only run free RCS
+            if power.map_or(step == 0, |mut power| power.draw(step)) {
+                acceleration += rcs.acceleration(heading.0);
+                burn += rcs.thrust.x.unsigned_abs() + rcs.thrust.y.unsigned_abs();
+            }
+        }
+
+        // Out of propellant, coast
+        if burn > 0
+            && let Some(mut propellant) = propellant
+            && !propellant.burn(burn)
+        {
+            continue;
+        }
 
-        velocity.0 += acceleration * factor * time.delta_secs();
+        velocity.0 = thrust_velocity(
+            velocity.0,
+            acceleration,
+            thrust.velocity_limit,
+            time.delta_secs(),
+        );
     }
 }
 
+// Velocity after accelerating for `delta` seconds
+pub fn thrust_velocity(
+    velocity: Vec2,
+    acceleration: Vec2,
+    velocity_limit: u32,
+    delta: f32,
+) -> Vec2 {
+    // Apply Lorentz factor only if it will increase the velocity,
+    // this is not realistic but permits easy deceleration for the ship
+    // Inspiration: https://stackoverflow.com/a/2891162
+    //
+    // NOTE: This will make direction change be sluggish unless the ship decelerate enough to
+    // do so. Could optionally allow for a heading change while preserving the current velocity
+    let factor = if velocity.dot(acceleration) >= 0.0 {
+        #[expect(clippy::cast_precision_loss)]
+        lorentz_factor(velocity, velocity_limit as f32)
+    } else {
+        1.0
+    };
+
+    velocity + acceleration * factor * delta
+}
+
+// Positions for the next `ticks` ticks if the ship keeps the same heading and acceleration, same
+// model as `apply_thrust` (physics moves the ship after the thrust is applied each tick)
+pub fn predict_path(
+    position: Vec2,
+    velocity: Vec2,
+    acceleration: Vec2,
+    velocity_limit: u32,
+    ticks: u32,
+) -> Vec<Vec2> {
+    let delta = 1.0 / TICK_HZ as f32;
+    let mut position = position;
+    let mut velocity = velocity;
+    (0..ticks)
+        .map(|_| {
+            velocity = thrust_velocity(velocity, acceleration, velocity_limit, delta);
+            position += velocity * delta;
+            position
+        })
+        .collect()
+}
+
 // Lorentz: Y = 1 / Sqrt(1 - v^2/c^2)
 //
 // vel: (0,0) == 1.0,
@@ -114,3 +282,48 @@ fn test_lorentz_factor() {
     // Check that sqrt(3/4) == exact 0.75
     assert_eq!(lorentz_factor(Vec2::new(0., 50.), 100.), 0.75_f32.sqrt());
 }
+
+#[test]
+fn test_rcs_acceleration() {
+    let mut rcs = Rcs::new(5, 3, 0);
+
+    // Limits are per axis
+    rcs.command(IVec2::new(10, -10));
+    assert_eq!(rcs.thrust, IVec2::new(5, -3));
+
+    // Facing north, starboard is east and reverse is south
+    assert_eq!(rcs.acceleration(AbsRot(0)), Vec2::new(5., -3.));
+
+    // Facing east, starboard is south and reverse is west
+    assert_eq!(rcs.acceleration(AbsRot(64)), Vec2::new(-3., -5.));
+}
+
+#[test]
+fn test_predict_path() {
+    // Coasting is a straight line, 64 ticks is a second
+    let path = predict_path(Vec2::ZERO, Vec2::new(64., 0.), Vec2::ZERO, 100, 64);
+    assert_eq!(path.len(), 64);
+    assert_eq!(path[0], Vec2::new(1., 0.));
+    assert_eq!(path[63], Vec2::new(64., 0.));
+
+    // Levels off around the velocity limit
+    let path = predict_path(Vec2::ZERO, Vec2::ZERO, Vec2::new(0., 1000.), 100, 640);
+    let speed = (path[639] - path[638]).length() * 64.;
+    assert!((speed - 100.).abs() < 5.);
+}
+
+#[test]
+fn test_propellant_burn() {
+    let mut propellant = Propellant::new(2);
+
+    // 64 per second == 1 per tick
+    assert!(propellant.burn(64));
+    assert!(propellant.burn(64));
+    assert_eq!(propellant.current, 0);
+
+    // Coasting is free
+    assert!(propellant.burn(0));
+
+    // Tank is dry
+    assert!(!propellant.burn(64));
+}
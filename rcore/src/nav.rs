@@ -0,0 +1,316 @@
+use bevy::math::I64Vec2;
+use bevy::prelude::*;
+
+use crate::TICK_HZ;
+use crate::math::AbsRot;
+use crate::math::RelRot;
+
+// Navigation helpers for ship scripts:
+// - Pure integer math on top of `math`, so a script gets the exact same answer as the sim.
+// - Rust scripts call the functions directly, VM scripts go through `ecall` with the syscall
+//   number in a7, the arguments in a0-a5 and the result in a0-a2.
+// - Times are in sim ticks, positions and velocities are in world units (per second).
+// - Scripts control every register, the math is done wide enough (or checked) that no input can
+//   overflow it, answers that don't fit come back as no answer.
+
+// Syscall numbers
+pub const NAV_BEARING: u32 = 0x100;
+pub const NAV_RANGE: u32 = 0x101;
+pub const NAV_INTERCEPT_TIME: u32 = 0x102;
+pub const NAV_LEAD_POINT: u32 = 0x103;
+pub const NAV_TIME_TO_TURN: u32 = 0x104;
+pub const NAV_STOPPING_DISTANCE: u32 = 0x105;
+
+// Returned in a0 when there is no answer (same point, no intercept, can't turn or stop)
+pub const NAV_NONE: u32 = u32::MAX;
+
+// Returned in a0 when the answer is in the other registers (NAV_LEAD_POINT)
+pub const NAV_OK: u32 = 0;
+
+// Returned in a0 for an unknown syscall, -ENOSYS like on linux
+pub const NAV_ENOSYS: u32 = (-38_i32).cast_unsigned();
+
+// Absolute bearing from base to target, None if they are on the same spot
+pub fn bearing(base: IVec2, target: IVec2) -> Option<AbsRot> {
+    AbsRot::from_vec2_angle(base, target)
+}
+
+// How far to turn from the current heading to face the target
+pub fn relative_bearing(heading: AbsRot, base: IVec2, target: IVec2) -> Option<RelRot> {
+    bearing(base, target).map(|bearing| heading.angle_between(bearing))
+}
+
+// Squares of i32 coordinates can top out past i64 once summed
+fn length_squared(vec: I64Vec2) -> i128 {
+    i128::from(vec.x).pow(2) + i128::from(vec.y).pow(2)
+}
+
+fn dot(a: I64Vec2, b: I64Vec2) -> i128 {
+    i128::from(a.x) * i128::from(b.x) + i128::from(a.y) * i128::from(b.y)
+}
+
+// Distance from base to target, rounded down
+pub fn range(base: IVec2, target: IVec2) -> i64 {
+    let offset = target.as_i64vec2() - base.as_i64vec2();
+    // At most ~2^32.5 apart, always fits
+    i64::try_from(length_squared(offset).isqrt()).unwrap_or(i64::MAX)
+}
+
+// Ticks till something launched at `speed` meets a target at `offset` moving at `velocity`,
+// both relative to the launcher. None if the target outruns it.
+//
+// Solves |offset + velocity * t| = speed * t for the earliest t >= 0
+pub fn intercept_time(offset: IVec2, velocity: IVec2, speed: i32) -> Option<u64> {
+    let offset = offset.as_i64vec2();
+    let velocity = velocity.as_i64vec2();
+    let tick_hz = i128::from(TICK_HZ);
+
+    // Quadratic in seconds: a*t^2 + b*t + c = 0
+    let a = length_squared(velocity) - i128::from(speed).pow(2);
+    let b = 2 * dot(offset, velocity);
+    let c = length_squared(offset);
+
+    if c == 0 {
+        return Some(0);
+    }
+
+    // Same speed as the target, only catches it if it is closing in
+    let ticks = if a == 0 {
+        (b < 0).then(|| (-c * tick_hz).div_euclid(b))?
+    } else {
+        // Both terms can reach ~2^128 with extreme inputs
+        let discriminant = b.checked_mul(b)?.checked_sub((4 * a).checked_mul(c)?)?;
+        if discriminant < 0 {
+            return None;
+        }
+        let root = discriminant.isqrt();
+        [(-b - root) * tick_hz, (-b + root) * tick_hz]
+            .into_iter()
+            .map(|t| t.div_euclid(2 * a))
+            .filter(|t| *t >= 0)
+            .min()?
+    };
+    u64::try_from(ticks).ok()
+}
+
+// Where to aim to hit the target, relative to the launcher, see `intercept_time`
+pub fn lead_point(offset: IVec2, velocity: IVec2, speed: i32) -> Option<IVec2> {
+    let ticks = i128::from(intercept_time(offset, velocity, speed)?);
+    // None if the meeting point is off the map of i32
+    let lead = |offset: i32, velocity: i32| {
+        i32::try_from(i128::from(offset) + i128::from(velocity) * ticks / i128::from(TICK_HZ)).ok()
+    };
+    Some(IVec2::new(
+        lead(offset.x, velocity.x)?,
+        lead(offset.y, velocity.y)?,
+    ))
+}
+
+// Ticks to turn from heading to target with a rotation limit (steps per second), this matches
+// the sim rotation with the sub-tick carry. None if the ship can't turn.
+pub fn time_to_turn(heading: AbsRot, target: AbsRot, limit: u16) -> Option<u64> {
+    let steps = u64::from(heading.angle_between(target).0.unsigned_abs());
+    if steps == 0 {
+        return Some(0);
+    }
+    if limit == 0 {
+        return None;
+    }
+    Some((steps * u64::from(TICK_HZ)).div_ceil(u64::from(limit)))
+}
+
+// Distance covered while braking to a stop, rounded up. None if the ship can't decelerate.
+//
+// The Lorentz factor only scales thrust that adds to the velocity, braking always gets the full
+// deceleration, so this is the plain v^2 / 2a.
+pub fn stopping_distance(velocity: IVec2, deceleration: i32) -> Option<i64> {
+    if deceleration == 0 {
+        return None;
+    }
+    let braking = 2 * i128::from(deceleration.unsigned_abs());
+    i64::try_from((length_squared(velocity.as_i64vec2()) + braking - 1) / braking).ok()
+}
+
+fn to_vec(x: u32, y: u32) -> IVec2 {
+    IVec2::new(x.cast_signed(), y.cast_signed())
+}
+
+fn to_reg<T: TryInto<u32>>(value: Option<T>) -> u32 {
+    value
+        .and_then(|value| value.try_into().ok())
+        .map_or(NAV_NONE, |value: u32| value.min(NAV_NONE - 1))
+}
+
+// VM script entry point, None for an unknown syscall so the host can trap on it.
+//
+// NAV_BEARING            a0-a1 base, a2-a3 target                 -> a0 bearing (0-255)
+// NAV_RANGE              a0-a1 base, a2-a3 target                 -> a0 range
+// NAV_INTERCEPT_TIME     a0-a1 offset, a2-a3 velocity, a4 speed   -> a0 ticks
+// NAV_LEAD_POINT         a0-a1 offset, a2-a3 velocity, a4 speed   -> a0 NAV_OK, a1-a2 lead point
+// NAV_TIME_TO_TURN       a0 heading, a1 target, a2 rotation limit -> a0 ticks
+// NAV_STOPPING_DISTANCE  a0-a1 velocity, a2 deceleration          -> a0 distance
+pub fn ecall(number: u32, args: [u32; 6]) -> Option<[u32; 3]> {
+    let [a0, a1, a2, a3, a4, _] = args;
+    let result = match number {
+        NAV_BEARING => [
+            to_reg(bearing(to_vec(a0, a1), to_vec(a2, a3)).map(|bearing| bearing.0)),
+            0,
+            0,
+        ],
+        NAV_RANGE => [to_reg(Some(range(to_vec(a0, a1), to_vec(a2, a3)))), 0, 0],
+        NAV_INTERCEPT_TIME => [
+            to_reg(intercept_time(
+                to_vec(a0, a1),
+                to_vec(a2, a3),
+                a4.cast_signed(),
+            )),
+            0,
+            0,
+        ],
+        // Every point is a valid answer, so the status goes in a0 on its own
+        NAV_LEAD_POINT => match lead_point(to_vec(a0, a1), to_vec(a2, a3), a4.cast_signed()) {
+            Some(lead) => [NAV_OK, lead.x.cast_unsigned(), lead.y.cast_unsigned()],
+            None => [NAV_NONE, 0, 0],
+        },
+        NAV_TIME_TO_TURN => [
+            to_reg(time_to_turn(
+                AbsRot(a0.to_le_bytes()[0]),
+                AbsRot(a1.to_le_bytes()[0]),
+                u16::try_from(a2).unwrap_or(u16::MAX),
+            )),
+            0,
+            0,
+        ],
+        NAV_STOPPING_DISTANCE => [
+            to_reg(stopping_distance(to_vec(a0, a1), a2.cast_signed())),
+            0,
+            0,
+        ],
+        _ => return None,
+    };
+    Some(result)
+}
+
+// Handler for `Emul32::run`, the unknown syscalls get NAV_ENOSYS back instead of trapping
+pub fn ecall_handler(number: u32, args: [u32; 6]) -> [u32; 3] {
+    ecall(number, args).unwrap_or([NAV_ENOSYS, 0, 0])
+}
+
+#[test]
+fn test_intercept() {
+    // Sitting target
+    assert_eq!(
+        intercept_time(IVec2::new(0, 1000), IVec2::ZERO, 100),
+        Some(640)
+    );
+
+    // Target crossing at 60/s, 3-4-5 triangle: 800 away, meet after 10 seconds
+    let offset = IVec2::new(0, 800);
+    let velocity = IVec2::new(60, 0);
+    assert_eq!(intercept_time(offset, velocity, 100), Some(640));
+    assert_eq!(
+        lead_point(offset, velocity, 100),
+        Some(IVec2::new(600, 800))
+    );
+
+    // Running away faster than the missile
+    assert_eq!(intercept_time(offset, IVec2::new(0, 200), 100), None);
+
+    // Same speed, closing in
+    assert_eq!(
+        intercept_time(IVec2::new(0, 1000), IVec2::new(0, -100), 100),
+        Some(320)
+    );
+}
+
+#[test]
+fn test_nav_ecall() {
+    // Due east
+    assert_eq!(ecall(NAV_BEARING, [0, 0, 10, 0, 0, 0]), Some([64, 0, 0]));
+    assert_eq!(
+        ecall(NAV_BEARING, [5, 5, 5, 5, 0, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+    assert_eq!(
+        ecall(NAV_RANGE, [0, 0, 3, (-4i32).cast_unsigned(), 0, 0]),
+        Some([5, 0, 0])
+    );
+
+    // Half turn at 16 steps per second
+    assert_eq!(
+        ecall(NAV_TIME_TO_TURN, [0, 128, 16, 0, 0, 0]),
+        Some([512, 0, 0])
+    );
+    assert_eq!(
+        ecall(NAV_TIME_TO_TURN, [0, 1, 0, 0, 0, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+
+    // 100/s braking at 20/s^2
+    assert_eq!(
+        ecall(NAV_STOPPING_DISTANCE, [0, 100, 20, 0, 0, 0]),
+        Some([250, 0, 0])
+    );
+
+    assert_eq!(ecall(0, [0; 6]), None);
+
+    // Same answers through the VM glue, the unknown syscalls get an error code instead
+    assert_eq!(ecall_handler(NAV_BEARING, [0, 0, 10, 0, 0, 0]), [64, 0, 0]);
+    assert_eq!(ecall_handler(0, [0; 6]), [NAV_ENOSYS, 0, 0]);
+}
+
+#[test]
+fn test_nav_ecall_lead_point() {
+    let neg = |value: i32| value.cast_unsigned();
+
+    // Sitting right next to the launcher, (-1, -1) is a real answer
+    assert_eq!(
+        ecall(NAV_LEAD_POINT, [neg(-1), neg(-1), 0, 0, 100, 0]),
+        Some([NAV_OK, neg(-1), neg(-1)])
+    );
+    // Target outruns it
+    assert_eq!(
+        ecall(NAV_LEAD_POINT, [0, 800, 0, 200, 100, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+}
+
+#[test]
+fn test_nav_ecall_extremes() {
+    let min = i32::MIN.cast_unsigned();
+    let max = i32::MAX.cast_unsigned();
+
+    // Would have been ~2^63 squared in i64
+    assert_eq!(
+        ecall(NAV_RANGE, [0, 0, min, min, 0, 0]),
+        Some([3_037_000_499, 0, 0])
+    );
+    // Opposite corners, ~2^32.5 apart, doesn't fit a register
+    assert_eq!(
+        ecall(NAV_RANGE, [max, max, min, min, 0, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+    assert_eq!(range(IVec2::MAX, IVec2::MIN), 6_074_000_998);
+
+    for args in [
+        [min, min, min, min, min, 0],
+        [max, max, min, min, max, 0],
+        [min, max, max, min, 0, 0],
+        [max, max, max, max, max, 0],
+    ] {
+        // Must not panic, whatever the answer is
+        let _ = ecall(NAV_INTERCEPT_TIME, args);
+        let _ = ecall(NAV_LEAD_POINT, args);
+        let _ = ecall(NAV_STOPPING_DISTANCE, args);
+    }
+
+    // The lead point is way past i32
+    assert_eq!(
+        ecall(NAV_LEAD_POINT, [max, max, max, max, max, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+    assert_eq!(
+        ecall(NAV_STOPPING_DISTANCE, [min, min, 1, 0, 0, 0]),
+        Some([NAV_NONE, 0, 0])
+    );
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::math::tick_step;
+
+// Ship power subsystem:
+// - Each ship has a reactor that generates power every tick into a capacitor bank
+// - Hardware draws from the bank (ie shield regeneration), if there isn't enough
+//   power the hardware just does not get it for that tick.
+pub struct PowerPlugin;
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            apply_power_generation.in_set(FixedGameSystem::GameLogic),
+        );
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Power {
+    pub current: u32,
+    pub maximum: u32,
+    // Power per second
+    pub generation: u32,
+    // Sub tick generation
+    pub carry: u32,
+}
+
+impl Power {
+    pub fn new(maximum: u32, generation: u32) -> Self {
+        Self {
+            current: maximum,
+            maximum,
+            generation,
+            carry: 0,
+        }
+    }
+
+    // All or nothing, returns if the power was drawn
+    pub fn draw(&mut self, amount: u32) -> bool {
+        if let Some(current) = self.current.checked_sub(amount) {
+            self.current = current;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub(crate) fn apply_power_generation(mut query: Query<&mut Power>) {
+    for mut power in query.iter_mut() {
+        // Bank is full, drop the carry
+        if power.current >= power.maximum {
+            if power.carry != 0 {
+                power.carry = 0;
+            }
+            continue;
+        }
+
+        let (step, carry) = tick_step(power.generation, power.carry, TICK_HZ);
+        power.carry = carry;
+        power.current = power.current.saturating_add(step).min(power.maximum);
+    }
+}
+
+#[test]
+fn test_power_draw() {
+    let mut power = Power::new(100, 10);
+
+    assert!(power.draw(60));
+    assert_eq!(power.current, 40);
+
+    // Not enough power, nothing is drawn
+    assert!(!power.draw(41));
+    assert_eq!(power.current, 40);
+
+    assert!(power.draw(40));
+    assert_eq!(power.current, 0);
+}
@@ -4,23 +4,56 @@ use avian2d::prelude::Position;
 use bevy::prelude::*;
 
 use crate::attach::AttachedTo;
+use crate::ecm::DECOY_SIGNATURE;
+use crate::ecm::Decoy;
+use crate::ecm::Jammer;
+use crate::ecm::apply_jammer;
 use crate::math::AbsRot;
+use crate::module::Module;
+use crate::module::ModuleKind;
 use crate::rotation::Heading;
 use crate::rotation::TargetHeading;
 use crate::rotation::apply_rotation;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::sleep::Sleep;
 
 use crate::FixedGameSystem;
+use crate::TICK_HZ;
+use crate::math::tick_step;
 
-// TODO: dynamic radar distance, for now fixed
+// Radar distance at the reference half-arc, the actual distance follows the arc width
 pub const DISTANCE: i64 = 4000;
 pub const DISTANCE_SQUARED: i64 = DISTANCE.pow(2);
+pub const REFERENCE_ARC: u8 = 32;
+// Narrowest beams are capped
+pub const MAX_DISTANCE: i64 = DISTANCE * 4;
+
+// Constant area rule, the beam covers the same area no matter the arc width, a narrow beam sees
+// far and a wide beam sees near.
+// - Beam width is 2 * half-arc + 1 (in 1/256th of a circle)
+// - width * distance^2 == reference width * DISTANCE^2
+pub fn arc_distance(arc: u8) -> i64 {
+    let area = DISTANCE_SQUARED * (2 * i64::from(REFERENCE_ARC) + 1);
+    (area / (2 * i64::from(arc) + 1)).isqrt().min(MAX_DISTANCE)
+}
+
+// Radar range against a given target, larger hulls shows up from further out
+// signature is a percentage of the radar distance
+pub fn signature_distance_squared(distance: i64, signature: u16) -> i64 {
+    (distance * i64::from(signature) / 100).pow(2)
+}
 
 pub struct RadarPlugin;
 impl Plugin for RadarPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<ContactMessage>().add_systems(
             FixedUpdate,
-            (apply_arc_width, apply_radar)
+            (
+                apply_arc_width,
+                apply_radar_range,
+                apply_radar.after(apply_jammer),
+            )
                 .chain()
                 .after(apply_rotation)
                 .in_set(FixedGameSystem::GameLogic),
@@ -33,6 +66,7 @@ impl Plugin for RadarPlugin {
 pub struct RadarBundle {
     pub radar: Radar,
     pub arc: ArcWidth,
+    pub module: Module,
     // Rotation
     pub heading: Heading,
     pub target: TargetHeading,
@@ -47,7 +81,11 @@ impl RadarBundle {
             arc: ArcWidth {
                 current: current_arc,
                 target: target_arc,
+                // NOTE: Insta arc changes, can adjust later
+                limit: u16::MAX,
+                carry: 0,
             },
+            module: Module::new(ModuleKind::Radar),
             // Rotation system
             heading: Heading(current),
             target: TargetHeading {
@@ -71,6 +109,10 @@ impl RadarBundle {
         self.arc.current = arc;
         self.arc.target = arc;
     }
+
+    pub fn arc_limit(&mut self, limit: u16) {
+        self.arc.limit = limit;
+    }
 }
 
 // Arc Width:
@@ -80,17 +122,39 @@ impl RadarBundle {
 // - 0 == 1/256th of an arc
 // - 1 == 3/256th of an arc
 // - 127 = 255/256th of an arc
-#[derive(Component, Clone, Copy, Default)]
+#[derive(Component, Clone, Copy)]
 pub struct ArcWidth {
     pub current: u8,
     pub target: u8,
+    pub limit: u16, // half-arc steps per second (>= 8192 is instant)
+    pub carry: u32, // sub tick arc change
+}
+
+impl Default for ArcWidth {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            target: 0,
+            limit: u16::MAX,
+            carry: 0,
+        }
+    }
 }
 
 #[derive(Component, Clone, Copy)]
 pub struct ArcDebug;
 
+// Detection distance of the radar, follows the arc width (see `arc_distance`)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RadarRange(pub i64);
+
+impl Default for RadarRange {
+    fn default() -> Self {
+        Self(DISTANCE)
+    }
+}
+
 // TODO:
-// - radar arc2length via area rule system?
 // - radar detection system -> emits contact events.
 // - Script subsystem listen for contact event and act upon it
 //
@@ -104,12 +168,12 @@ pub struct ArcDebug;
 //  * optimization (check enemy contacts only)
 // - These within a certain distance, are then checked again for their angle
 // - This will then be compared to the radar angle (is it within?), if so
-// - This final list will be all of the entities that are 'detected' by the radar, we can then deal
-// with ECM and any other warfare stuff later
+// - This final list will be all of the entities that are 'detected' by the radar, enemy jammers
+// shrinks the detection distance and enemy decoys shows up alongside the ships (see ecm)
 // - This approach is basically "converting" each entities into a polaris coordination from your
 // ship/radar
 #[derive(Component, Clone, Copy)]
-#[require(ArcWidth)]
+#[require(ArcWidth, RadarRange)]
 pub struct Radar;
 
 #[derive(Component, Clone, Copy)]
@@ -137,40 +201,97 @@ pub enum ArcCheck {
 }
 
 // Handle arc width changes:
-// - Instant (like headings right now)A
+// - Rate limited (like headings)
+// - Only touch it upon changes, the shield collider is rebuilt on arc changes
 pub(crate) fn apply_arc_width(mut query: Query<&mut ArcWidth>) {
     for mut arc in query.iter_mut() {
-        arc.current = arc.target;
+        // If arc is the same as the target arc, bail
+        if arc.current == arc.target {
+            // Drop the carry
+            if arc.carry != 0 {
+                arc.carry = 0;
+            }
+            continue;
+        }
+
+        // Calculate the carry so sub-tick arc changes isn't lost
+        let (step, carry) = tick_step(u32::from(arc.limit), arc.carry, TICK_HZ);
+        arc.carry = carry;
+
+        let step = u8::try_from(step).unwrap_or(u8::MAX);
+        arc.current = if arc.current < arc.target {
+            arc.current.saturating_add(step).min(arc.target)
+        } else {
+            arc.current.saturating_sub(step).max(arc.target)
+        };
+    }
+}
+
+// Only touch it upon arc changes
+#[expect(clippy::type_complexity)]
+pub(crate) fn apply_radar_range(
+    mut query: Query<(&ArcWidth, &mut RadarRange), (With<Radar>, Changed<ArcWidth>)>,
+) {
+    for (arc, mut range) in query.iter_mut() {
+        range.0 = arc_distance(arc.current);
     }
 }
 
 // TODO: split this and setup system ordering but for now.
 pub(crate) fn apply_radar(
     mut message: MessageWriter<ContactMessage>,
-    query: Query<(&Heading, &ArcWidth, &AttachedTo), With<Radar>>,
-    ship_query: Query<(Entity, &Position)>,
+    query: Query<(&Heading, &ArcWidth, &RadarRange, &AttachedTo), With<Radar>>,
+    ship_query: Query<(Entity, &Position, &Ship, &Faction)>,
+    sleep_query: Query<(), With<Sleep>>,
+    jammer_query: Query<(&Jammer, &Position, &Heading, &Faction)>,
+    decoy_query: Query<(Entity, &Position, &Faction), With<Decoy>>,
 ) {
-    for (heading, arc, attached_to) in query.iter() {
+    for (heading, arc, range, attached_to) in query.iter() {
         // Scan through all target on field, and calculate their distance and angle,
         // if within the arc store it in a list till we know the closest contact
         let mut best_target: Option<(Entity, IVec2)> = None;
 
-        // Tolerate a missing parent; module-stripping damage is a todo
-        let Ok((base_ship, base_position)) = ship_query.get(attached_to.0) else {
+        // Tolerate a missing parent, the ship may have been despawned this tick
+        let Ok((base_ship, base_position, _, base_faction)) = ship_query.get(attached_to.0) else {
             continue;
         };
+        // Radar is off while the ship sleeps
+        if sleep_query.contains(base_ship) {
+            continue;
+        }
         let base = base_position.0.as_ivec2();
 
+        // Enemy jammer pointed at us, only the close contacts burns through the noise
+        let distance = jammer_query
+            .iter()
+            .filter(|(jammer, pos, heading, faction)| {
+                jammer.jamming
+                    && *faction != base_faction
+                    && jammer.covers(pos.0.as_ivec2(), heading.0, base)
+            })
+            .map(|(jammer, ..)| jammer.burn_through)
+            .fold(range.0, i64::min);
+
+        // Own decoys are known to be fake
+        let ships = ship_query
+            .iter()
+            .map(|(entity, pos, class, _)| (entity, pos, class.0.stats().radar_signature));
+        let decoys = decoy_query
+            .iter()
+            .filter(|(_, _, faction)| *faction != base_faction)
+            .map(|(entity, pos, _)| (entity, pos, DECOY_SIGNATURE));
+
         // TODO: abstract this logic to a helper class (gizmo debug wants this too and we will have
         // other radar types)
-        for (target_ship, target_position) in ship_query.iter() {
+        for (target_ship, target_position, signature) in ships.chain(decoys) {
             if base_ship == target_ship {
                 continue;
             }
             let target = target_position.0.as_ivec2();
+            let distance_squared = signature_distance_squared(distance, signature);
 
             if matches!(
-                within_radar(base, target, heading.0, arc.current, DISTANCE_SQUARED),
+                within_radar(base, target, heading.0, arc.current, distance_squared),
                 RadarContact::Contact
             ) {
                 // Is this contact better than current winner?
@@ -226,3 +347,20 @@ pub fn within_arc(base: IVec2, target: IVec2, heading: AbsRot, arc: u8) -> ArcCh
         None => ArcCheck::SamePosition,
     }
 }
+
+#[test]
+fn test_arc_distance() {
+    // Reference arc
+    assert_eq!(arc_distance(REFERENCE_ARC), DISTANCE);
+
+    // Narrow sees further, wide sees nearer
+    assert!(arc_distance(16) > DISTANCE);
+    assert_eq!(arc_distance(127), 2019);
+
+    // Narrowest beam is capped
+    assert_eq!(arc_distance(0), MAX_DISTANCE);
+
+    // Same area (within rounding)
+    let area = |arc: u8| arc_distance(arc).pow(2) * (2 * i64::from(arc) + 1);
+    assert!((area(64) - area(REFERENCE_ARC)).abs() * 1000 < area(REFERENCE_ARC));
+}
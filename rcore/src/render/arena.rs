@@ -6,6 +6,7 @@ use bevy_prototype_lyon::prelude::Stroke;
 
 use bevy::prelude::*;
 
+use crate::ARENA_SIZE;
 use crate::math::AbsRot;
 
 // TODO: add an Arena Marker for ships and stuff for objects we want to have warping
@@ -14,7 +15,7 @@ use crate::math::AbsRot;
 struct ArenaMarker;
 
 pub(super) fn arena_bounds_setup(mut commands: Commands) {
-    let display = Vec2::new(10240., 6400.);
+    let display = ARENA_SIZE;
 
     // Arena Bounds
     let path = ShapePath::new()
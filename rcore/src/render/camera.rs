@@ -2,6 +2,9 @@ use avian2d::interpolation::TransformEasingSystems;
 use avian2d::schedule::PhysicsSystems;
 use bevy::prelude::*;
 
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
 // Input for camera
 use bevy::color::palettes::css::FUCHSIA;
 use bevy::input::ButtonInput;
@@ -9,6 +12,16 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
 use bevy::window::PrimaryWindow;
 
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::stats::SimEvent;
+use crate::stats::SimEventKind;
+
+use super::input::BOOKMARKS;
+use super::input::InputAction;
+use super::input::InputMap;
+use super::pointer_over_ui;
+
 // TODO: Camera refinement
 // 1. make it so that the camera stays within the area bounds instead of just the target
 // 2. make it so that when the camera is about to hit the areana edge, slow down to a stop so its
@@ -43,13 +56,23 @@ impl Plugin for CameraPlugin {
         app.add_systems(Startup, camera_setup)
             // TODO: System ordering:
             // 1. update the input/target/etc in Update (or Fixed Update)
-            .add_systems(Update, (render_camera_focus, update_camera_focus))
+            .add_systems(
+                Update,
+                (
+                    render_camera_focus,
+                    update_camera_focus,
+                    cycle_follow_target,
+                    camera_bookmarks,
+                    record_combat,
+                ),
+            )
             // 2. PostUpdate (after iterpolotation) do the camera-rig updates
             .add_systems(
                 PostUpdate,
                 (
                     resolve_drag_pan,
                     resolve_follow_mode.run_if(rig_in_follow_mode),
+                    resolve_auto_mode.run_if(rig_in_auto_mode),
                     // constrain_camera,
                     apply_camera_rig,
                 )
@@ -62,8 +85,9 @@ impl Plugin for CameraPlugin {
     }
 }
 
+// Default UI camera, the minimap camera draws over it and would otherwise take the UI
 #[derive(Component)]
-#[require(Camera2d)]
+#[require(Camera2d, IsDefaultUiCamera)]
 pub struct CameraRig {
     pub mode: CameraMode,
     config: CameraConfig,
@@ -77,6 +101,20 @@ pub struct CameraRig {
     drag_cursor: Option<Vec2>,
     // World-space drag-pan anchor, for crisp 1:1 drag
     drag_anchor: Option<Vec2>,
+    // Left button went down over the UI, no drag-pan till it's released
+    ui_press: bool,
+    // Auto mode state
+    director: Director,
+    // Saved (focus, zoom factor) per slot
+    bookmarks: [Option<(Vec2, f32)>; BOOKMARKS as usize],
+}
+
+impl CameraRig {
+    // Jump the focus to a world position and take back manual control
+    pub fn recenter(&mut self, focus: Vec2) {
+        self.mode = CameraMode::Free;
+        self.focus = focus;
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -85,8 +123,8 @@ pub enum CameraMode {
     Free,
     // Follow a entity
     Follow(Entity),
-    // Additional Modes such as: Auto
-    // TODO: Auto mode ideas:
+    // Director for spectating, frames the fight on its own
+    Auto,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -103,12 +141,73 @@ pub struct CameraConfig {
     zoom_step_per_line: f32,
     zoom_step_per_pixel: f32,
     zoom_decay_rate: f32,
+    // Zoom per second while the zoom key is held
+    zoom_key_speed: f32,
     // Edge panning config
     // The margin around the edge of the screen for panning, if
     // camera is barely into it, move slowly but if its far into it, move faster
     edge_margin: Vec2,
     edge_speed: f32,
     edge_speed_max: f32,
+    // Auto mode config
+    // Shortest time (seconds) on a shot before cutting to something else
+    director_min_shot: f32,
+    // How long (seconds) combat stays hot
+    director_window: f32,
+    // World units of room around the framed ships
+    director_padding: f32,
+}
+
+// Ortho projection scale at zoom 0
+const ZOOM_BASE_SCALE: f32 = 10.0;
+
+// Recent combat for the auto mode to pick its shots from
+#[derive(Debug, Default)]
+struct Director {
+    // (real time, source, target, weight), oldest first
+    heat: VecDeque<(f32, Option<Entity>, Option<Entity>, f32)>,
+    // Who the current shot is on, None takes in the whole field
+    subject: Option<Entity>,
+    // Real time the current shot started at
+    shot_start: f32,
+}
+
+impl Director {
+    // Most combat weight in the window, newer events count for more
+    fn hottest(&self, now: f32, window: f32, alive: impl Fn(Entity) -> bool) -> Option<Entity> {
+        let mut scores: BTreeMap<Entity, f32> = BTreeMap::new();
+        for &(time, source, target, weight) in &self.heat {
+            let heat = weight * (1.0 - (now - time) / window).max(0.0);
+            for entity in [source, target].into_iter().flatten() {
+                *scores.entry(entity).or_default() += heat;
+            }
+        }
+        scores
+            .into_iter()
+            .filter(|(entity, score)| *score > 0.0 && alive(*entity))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+    }
+
+    // Who the subject has been fighting with
+    fn partners(&self, subject: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.heat
+            .iter()
+            .filter_map(move |&(_, source, target, _)| match (source, target) {
+                (Some(source), other) if source == subject => other,
+                (other, Some(target)) if target == subject => other,
+                _ => None,
+            })
+    }
+}
+
+// Kills and hits draw the eye more than shots fired
+const fn heat_weight(kind: SimEventKind) -> f32 {
+    match kind {
+        SimEventKind::Kill => 8.0,
+        SimEventKind::Hit | SimEventKind::Collision => 2.0,
+        SimEventKind::Shot | SimEventKind::ShieldAbsorb | SimEventKind::Launch => 1.0,
+    }
 }
 
 pub fn camera_setup(mut commands: Commands) {
@@ -134,16 +233,25 @@ pub fn camera_setup(mut commands: Commands) {
             zoom_step_per_pixel: 0.25 / 45.0,
             // 4 ~= supcom floaty, 8 ~= smooth, 12 ~= snappy but has some animation, 15 ~= crisp
             zoom_decay_rate: 12.0,
+            // Full zoom range in 2 seconds
+            zoom_key_speed: 2.0,
             // Margin around the edge of the window
             edge_margin: Vec2::splat(30.0),
             edge_speed: 0.05,
             edge_speed_max: 1000.0,
+            // Long enough to see the shot land, short enough to not miss the next one
+            director_min_shot: 3.0,
+            director_window: 6.0,
+            director_padding: 1500.0,
         },
         focus: Vec2::new(0.0, 0.0),
         zoom_factor: 0.0,
         zoom_scale: 1.0,
         drag_cursor: None,
         drag_anchor: None,
+        ui_press: false,
+        director: Director::default(),
+        bookmarks: [None; BOOKMARKS as usize],
     });
 }
 
@@ -153,18 +261,33 @@ fn render_camera_focus(mut gizmos: Gizmos, query: Query<&CameraRig, With<Camera2
     }
 }
 
+#[expect(clippy::too_many_arguments)]
 fn update_camera_focus(
     time: Res<Time<Real>>,
     mut rig: Single<&mut CameraRig, With<Camera2d>>,
     key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mouse_wheel_input: Res<AccumulatedMouseScroll>,
     window_input: Single<&Window, With<PrimaryWindow>>,
+    interactions: Query<&Interaction>,
 ) {
     let config = rig.config;
 
+    // Deal with zoom from the keyboard
+    let mut zoom = 0.0;
+    if input_map.pressed(InputAction::ZoomIn, &key_input) {
+        zoom -= 1.0;
+    }
+    if input_map.pressed(InputAction::ZoomOut, &key_input) {
+        zoom += 1.0;
+    }
+    if zoom != 0.0 {
+        rig.zoom_factor = (rig.zoom_factor + zoom * config.zoom_key_speed * time.delta_secs())
+            .clamp(config.zoom_clamp.0, config.zoom_clamp.1);
+    }
+
     // Deal with zoom from the mouse
-    // TODO: support keyboard zoom
     if mouse_wheel_input.delta != Vec2::ZERO {
         let step = match mouse_wheel_input.unit {
             MouseScrollUnit::Line => mouse_wheel_input.delta.y * config.zoom_step_per_line,
@@ -184,8 +307,16 @@ fn update_camera_focus(
     //
     // If a higher priority option happen, it overrides the lower
 
+    // Hand the camera to the director
+    if input_map.just_pressed(InputAction::CameraAuto, &key_input) {
+        rig.mode = CameraMode::Auto;
+    }
+
     // Drag-pan (anchor-based)
-    if mouse_input.pressed(MouseButton::Left) {
+    if mouse_input.just_pressed(MouseButton::Left) {
+        rig.ui_press = pointer_over_ui(&interactions);
+    }
+    if mouse_input.pressed(MouseButton::Left) && !rig.ui_press {
         // If we got input, the mouse *should* be within the window, but you know....
         rig.drag_cursor = window_input.cursor_position();
 
@@ -245,16 +376,16 @@ fn update_camera_focus(
     // Wasd-pan
     let mut direction = Vec2::ZERO;
 
-    if key_input.pressed(KeyCode::KeyW) {
+    if input_map.pressed(InputAction::PanUp, &key_input) {
         direction.y += 1.;
     }
-    if key_input.pressed(KeyCode::KeyS) {
+    if input_map.pressed(InputAction::PanDown, &key_input) {
         direction.y -= 1.;
     }
-    if key_input.pressed(KeyCode::KeyA) {
+    if input_map.pressed(InputAction::PanLeft, &key_input) {
         direction.x -= 1.;
     }
-    if key_input.pressed(KeyCode::KeyD) {
+    if input_map.pressed(InputAction::PanRight, &key_input) {
         direction.x += 1.;
     }
     if direction != Vec2::ZERO {
@@ -264,6 +395,73 @@ fn update_camera_focus(
     }
 }
 
+// Step the follow target through the ships, entity order wraps around
+fn cycle_follow_target(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut rig: Single<&mut CameraRig, With<Camera2d>>,
+    query: Query<(Entity, &Faction), With<Ship>>,
+) {
+    let mut ships: Vec<(Faction, Entity)> = query
+        .iter()
+        .map(|(entity, faction)| (*faction, entity))
+        .collect();
+    if ships.is_empty() {
+        return;
+    }
+    let current = match rig.mode {
+        CameraMode::Follow(target) => query
+            .get(target)
+            .ok()
+            .map(|(_, faction)| (*faction, target)),
+        _ => None,
+    };
+
+    let target = if input_map.just_pressed(InputAction::FollowNext, &key_input) {
+        ships.sort_by_key(|(_, entity)| *entity);
+        current
+            .and_then(|(_, target)| ships.iter().find(|(_, entity)| *entity > target))
+            .or_else(|| ships.first())
+    } else if input_map.just_pressed(InputAction::FollowPrevious, &key_input) {
+        ships.sort_by_key(|(_, entity)| *entity);
+        current
+            .and_then(|(_, target)| ships.iter().rev().find(|(_, entity)| *entity < target))
+            .or_else(|| ships.last())
+    } else if input_map.just_pressed(InputAction::FollowFaction, &key_input) {
+        // Sorted by faction then entity, so the first ship of the next faction
+        ships.sort();
+        current
+            .and_then(|(current, _)| ships.iter().find(|(faction, _)| *faction > current))
+            .or_else(|| ships.first())
+    } else {
+        return;
+    };
+
+    if let Some((_, target)) = target {
+        rig.mode = CameraMode::Follow(*target);
+    }
+}
+
+// Save the camera view to a slot, or jump back to a saved one
+fn camera_bookmarks(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut rig: Single<&mut CameraRig, With<Camera2d>>,
+) {
+    for slot in 0..BOOKMARKS {
+        let index = usize::from(slot);
+        if input_map.just_pressed(InputAction::BookmarkSave(slot), &key_input) {
+            rig.bookmarks[index] = Some((rig.focus, rig.zoom_factor));
+        }
+        if input_map.just_pressed(InputAction::BookmarkRecall(slot), &key_input)
+            && let Some((focus, zoom_factor)) = rig.bookmarks[index]
+        {
+            rig.recenter(focus);
+            rig.zoom_factor = zoom_factor;
+        }
+    }
+}
+
 // Utility for run_if
 fn rig_in_follow_mode(rig: Single<&CameraRig>) -> bool {
     matches!(rig.mode, CameraMode::Follow(_))
@@ -295,6 +493,87 @@ fn resolve_follow_mode(
     }
 }
 
+fn rig_in_auto_mode(rig: Single<&CameraRig>) -> bool {
+    rig.mode == CameraMode::Auto
+}
+
+// Keep track of the fight even outside of the auto mode so it can cut right in
+fn record_combat(
+    time: Res<Time<Real>>,
+    mut events: MessageReader<SimEvent>,
+    mut rig: Single<&mut CameraRig, With<Camera2d>>,
+) {
+    let now = time.elapsed_secs();
+    let window = rig.config.director_window;
+    let heat = &mut rig.director.heat;
+
+    for event in events.read() {
+        heat.push_back((now, event.source, event.target, heat_weight(event.kind)));
+    }
+    while heat.front().is_some_and(|(time, ..)| now - time > window) {
+        heat.pop_front();
+    }
+}
+
+fn resolve_auto_mode(
+    time: Res<Time<Real>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut rig: Single<&mut CameraRig, With<Camera2d>>,
+    ships: Query<&Transform, With<Ship>>,
+) {
+    let rig: &mut CameraRig = &mut rig;
+    let config = rig.config;
+    let now = time.elapsed_secs();
+    let director = &mut rig.director;
+
+    // Hold each shot for a while so the camera doesn't jitter between fights
+    if now - director.shot_start >= config.director_min_shot {
+        let hottest =
+            director.hottest(now, config.director_window, |entity| ships.contains(entity));
+        if hottest != director.subject {
+            director.subject = hottest;
+            director.shot_start = now;
+        }
+    }
+
+    // Frame the subject and whoever it's been fighting with
+    let position = |entity| {
+        ships
+            .get(entity)
+            .ok()
+            .map(|tran| tran.translation.truncate())
+    };
+    let mut framed: Vec<Vec2> = director
+        .subject
+        .into_iter()
+        .flat_map(|subject| std::iter::once(subject).chain(director.partners(subject)))
+        .filter_map(position)
+        .collect();
+
+    // Nothing going on (or the whole fight is gone), take in all of the living ships
+    if framed.is_empty() {
+        framed = ships
+            .iter()
+            .map(|tran| tran.translation.truncate())
+            .collect();
+    }
+    let Some(bounds) = framed
+        .into_iter()
+        .map(|pos| Rect::from_center_size(pos, Vec2::ZERO))
+        .reduce(|bounds, pos| bounds.union(pos))
+    else {
+        return;
+    };
+
+    // Zoom out till it all fits, the rig takes care of the smoothing
+    let size = bounds.size() + Vec2::splat(config.director_padding * 2.0);
+    let scale = (size / window.size()).max_element();
+    rig.focus = bounds.center();
+    rig.zoom_factor = (scale / ZOOM_BASE_SCALE)
+        .log2()
+        .clamp(config.zoom_clamp.0, config.zoom_clamp.1);
+}
+
 fn resolve_drag_pan(camera: Single<(&Camera, &GlobalTransform, &mut CameraRig), With<Camera2d>>) {
     // NOTE: Since this is ran pre TransformSystem::Propagate
     // the GlobalTransform has the previous frame transform
@@ -343,10 +622,9 @@ fn apply_camera_rig(
 
     // Apply camera zoom
     if let Projection::Orthographic(ref mut ortho) = *proj {
-        let arena_scale = 10.;
         let zoom_factor = rig.zoom_factor;
         rig.zoom_scale
             .smooth_nudge(&zoom_factor, config.zoom_decay_rate, time.delta_secs());
-        ortho.scale = arena_scale * rig.zoom_scale.exp2();
+        ortho.scale = ZOOM_BASE_SCALE * rig.zoom_scale.exp2();
     }
 }
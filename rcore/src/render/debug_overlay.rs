@@ -0,0 +1,384 @@
+use bevy::prelude::*;
+
+use crate::attach::Attachments;
+use crate::movement::MovDebug;
+use crate::radar::ArcDebug;
+use crate::radar::Radar;
+use crate::radar::RadarDebug;
+use crate::rotation::RotDebug;
+use crate::ship::Ship;
+use crate::weapon::HealthDebug;
+use crate::weapon::Shield;
+use crate::weapon::ShieldHealthDebug;
+
+use super::input::InputAction;
+use super::input::InputMap;
+use super::inspector::Selected;
+
+// Debug overlay:
+// - Turns the debug gizmos on and off at runtime by inserting and removing the debug marker
+//   components, same ones `ShipBuilder::debug` sets at spawn.
+// - Applies to the selected ship or to all ships (scope), a toggle turns the marker on everywhere
+//   unless it's already on everywhere, then it turns it off.
+// - Driven from the panel buttons or the hotkeys, the panel shows the state for the scope.
+pub(super) struct DebugOverlayPlugin;
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugScope>()
+            .add_message::<DebugToggleMessage>()
+            .add_systems(Startup, setup_debug_panel)
+            .add_systems(
+                Update,
+                (
+                    debug_hotkeys,
+                    debug_buttons,
+                    apply_debug_toggle,
+                    render_debug_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugToggle {
+    Radar,
+    RadarArc,
+    Movement,
+    Rotation,
+    Health,
+    ShieldHealth,
+    ShieldArc,
+}
+
+// What the marker goes on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DebugHost {
+    Ship,
+    Radar,
+    Shield,
+}
+
+impl DebugToggle {
+    pub const ALL: [Self; 7] = [
+        Self::Radar,
+        Self::RadarArc,
+        Self::Movement,
+        Self::Rotation,
+        Self::Health,
+        Self::ShieldHealth,
+        Self::ShieldArc,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Radar => "radar",
+            Self::RadarArc => "radar_arc",
+            Self::Movement => "movement",
+            Self::Rotation => "rotation",
+            Self::Health => "health",
+            Self::ShieldHealth => "shield_health",
+            Self::ShieldArc => "shield_arc",
+        }
+    }
+
+    const fn host(self) -> DebugHost {
+        match self {
+            Self::Radar | Self::RadarArc => DebugHost::Radar,
+            Self::Movement | Self::Rotation | Self::Health => DebugHost::Ship,
+            Self::ShieldHealth | Self::ShieldArc => DebugHost::Shield,
+        }
+    }
+
+    fn has(self, markers: &Markers) -> bool {
+        match self {
+            Self::Radar => markers.radar,
+            Self::RadarArc | Self::ShieldArc => markers.arc,
+            Self::Movement => markers.movement,
+            Self::Rotation => markers.rotation,
+            Self::Health => markers.health,
+            Self::ShieldHealth => markers.shield_health,
+        }
+    }
+
+    fn set(self, commands: &mut Commands, entity: Entity, on: bool) {
+        let mut entity = commands.entity(entity);
+        match (self, on) {
+            (Self::Radar, true) => entity.insert(RadarDebug),
+            (Self::Radar, false) => entity.remove::<RadarDebug>(),
+            (Self::RadarArc | Self::ShieldArc, true) => entity.insert(ArcDebug),
+            (Self::RadarArc | Self::ShieldArc, false) => entity.remove::<ArcDebug>(),
+            (Self::Movement, true) => entity.insert(MovDebug),
+            (Self::Movement, false) => entity.remove::<MovDebug>(),
+            (Self::Rotation, true) => entity.insert(RotDebug),
+            (Self::Rotation, false) => entity.remove::<RotDebug>(),
+            (Self::Health, true) => entity.insert(HealthDebug),
+            (Self::Health, false) => entity.remove::<HealthDebug>(),
+            (Self::ShieldHealth, true) => entity.insert(ShieldHealthDebug),
+            (Self::ShieldHealth, false) => entity.remove::<ShieldHealthDebug>(),
+        };
+    }
+}
+
+// Which ships the toggles apply to
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DebugScope {
+    #[default]
+    Selected,
+    All,
+}
+
+// Flip the debug marker for the ships in scope
+#[derive(Message, Copy, Clone, Debug)]
+pub struct DebugToggleMessage(pub DebugToggle);
+
+// Debug markers on one entity
+struct Markers {
+    radar: bool,
+    arc: bool,
+    movement: bool,
+    rotation: bool,
+    health: bool,
+    shield_health: bool,
+}
+
+type MarkerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Has<RadarDebug>,
+        Has<ArcDebug>,
+        Has<MovDebug>,
+        Has<RotDebug>,
+        Has<HealthDebug>,
+        Has<ShieldHealthDebug>,
+        Has<Radar>,
+        Has<Shield>,
+    ),
+>;
+
+// Marker state and host kinds of the entity
+fn markers(query: &MarkerQuery, entity: Entity) -> Option<(Markers, Option<DebugHost>)> {
+    let (radar, arc, movement, rotation, health, shield_health, is_radar, is_shield) =
+        query.get(entity).ok()?;
+    let host = if is_radar {
+        Some(DebugHost::Radar)
+    } else if is_shield {
+        Some(DebugHost::Shield)
+    } else {
+        None
+    };
+    Some((
+        Markers {
+            radar,
+            arc,
+            movement,
+            rotation,
+            health,
+            shield_health,
+        },
+        host,
+    ))
+}
+
+// Entities in scope that the toggle goes on, with whether they have it on already
+fn toggle_targets(
+    toggle: DebugToggle,
+    scope: DebugScope,
+    selected: &Selected,
+    ship_query: &Query<(Entity, Option<&Attachments>), With<Ship>>,
+    marker_query: &MarkerQuery,
+) -> Vec<(Entity, bool)> {
+    let ships: Vec<_> = match scope {
+        DebugScope::Selected => selected
+            .0
+            .and_then(|entity| ship_query.get(entity).ok())
+            .into_iter()
+            .collect(),
+        DebugScope::All => ship_query.iter().collect(),
+    };
+
+    ships
+        .into_iter()
+        .flat_map(|(ship, attachments)| {
+            std::iter::once(ship).chain(attachments.into_iter().flat_map(RelationshipTarget::iter))
+        })
+        .filter_map(|entity| {
+            let (markers, host) = markers(marker_query, entity)?;
+            let host = host.unwrap_or(DebugHost::Ship);
+            // Ship markers only go on the ship itself, not the other modules
+            let fits = match toggle.host() {
+                DebugHost::Ship => ship_query.contains(entity),
+                other => other == host,
+            };
+            fits.then(|| (entity, toggle.has(&markers)))
+        })
+        .collect()
+}
+
+#[derive(Component)]
+struct DebugPanel;
+
+#[derive(Component, Clone, Copy)]
+enum DebugButton {
+    Scope,
+    Toggle(DebugToggle),
+}
+
+fn setup_debug_panel(mut commands: Commands) {
+    let buttons: Vec<DebugButton> = std::iter::once(DebugButton::Scope)
+        .chain(DebugToggle::ALL.map(DebugButton::Toggle))
+        .collect();
+
+    commands
+        .spawn((
+            DebugPanel,
+            // Block the clicks from reaching the camera
+            Interaction::default(),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.),
+                top: Val::Px(60.),
+                padding: UiRect::all(Val::Px(6.)),
+                row_gap: Val::Px(4.),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.05, 0.85)),
+        ))
+        .with_children(|parent| {
+            for button in buttons {
+                parent.spawn((
+                    Button,
+                    button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.), Val::Px(2.)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    children![(
+                        Text::new(""),
+                        TextFont {
+                            font_size: FontSize::Px(14.),
+                            ..default()
+                        },
+                    )],
+                ));
+            }
+        });
+}
+
+fn debug_hotkeys(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut scope: ResMut<DebugScope>,
+    mut panel: Single<&mut Node, With<DebugPanel>>,
+    mut message: MessageWriter<DebugToggleMessage>,
+) {
+    for toggle in DebugToggle::ALL {
+        if input_map.just_pressed(InputAction::ToggleDebug(toggle), &key_input) {
+            message.write(DebugToggleMessage(toggle));
+        }
+    }
+    if input_map.just_pressed(InputAction::DebugScope, &key_input) {
+        *scope = scope.next();
+    }
+    if input_map.just_pressed(InputAction::DebugPanel, &key_input) {
+        panel.display = match panel.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+impl DebugScope {
+    const fn next(self) -> Self {
+        match self {
+            Self::Selected => Self::All,
+            Self::All => Self::Selected,
+        }
+    }
+}
+
+fn debug_buttons(
+    mut scope: ResMut<DebugScope>,
+    query: Query<(&Interaction, &DebugButton), Changed<Interaction>>,
+    mut message: MessageWriter<DebugToggleMessage>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            DebugButton::Scope => *scope = scope.next(),
+            DebugButton::Toggle(toggle) => {
+                message.write(DebugToggleMessage(*toggle));
+            }
+        }
+    }
+}
+
+fn apply_debug_toggle(
+    mut commands: Commands,
+    mut messages: MessageReader<DebugToggleMessage>,
+    scope: Res<DebugScope>,
+    selected: Res<Selected>,
+    ship_query: Query<(Entity, Option<&Attachments>), With<Ship>>,
+    marker_query: MarkerQuery,
+) {
+    for DebugToggleMessage(toggle) in messages.read() {
+        let targets = toggle_targets(*toggle, *scope, &selected, &ship_query, &marker_query);
+        // On everywhere unless it's already on everywhere
+        let on = !targets.iter().all(|(_, on)| *on);
+        for (entity, _) in targets {
+            toggle.set(&mut commands, entity, on);
+        }
+    }
+}
+
+fn render_debug_panel(
+    scope: Res<DebugScope>,
+    selected: Res<Selected>,
+    input_map: Res<InputMap>,
+    button_query: Query<(&DebugButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+    ship_query: Query<(Entity, Option<&Attachments>), With<Ship>>,
+    marker_query: MarkerQuery,
+) {
+    // First bound key as a hint
+    let hint = |action| {
+        input_map
+            .bindings(action)
+            .next()
+            .map_or_else(String::new, |binding| format!(" [{}]", binding.name()))
+    };
+
+    for (button, children) in button_query.iter() {
+        let label = match button {
+            DebugButton::Scope => format!("Scope: {:?}{}", *scope, hint(InputAction::DebugScope)),
+            DebugButton::Toggle(toggle) => {
+                let targets =
+                    toggle_targets(*toggle, *scope, &selected, &ship_query, &marker_query);
+                let on = targets.iter().filter(|(_, on)| *on).count();
+                let state = match on {
+                    0 => "off",
+                    on if on == targets.len() => "on",
+                    _ => "mixed",
+                };
+                format!(
+                    "{}: {state}{}",
+                    toggle.name(),
+                    hint(InputAction::ToggleDebug(*toggle))
+                )
+            }
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child)
+                && text.0 != label
+            {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
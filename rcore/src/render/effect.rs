@@ -0,0 +1,270 @@
+use bevy_prototype_lyon::geometry::ShapeBuilderBase as _;
+use bevy_prototype_lyon::prelude::Fill;
+use bevy_prototype_lyon::prelude::LineCap;
+use bevy_prototype_lyon::prelude::Shape;
+use bevy_prototype_lyon::prelude::ShapeBuilder;
+use bevy_prototype_lyon::prelude::ShapePath;
+use bevy_prototype_lyon::prelude::Stroke;
+use bevy_prototype_lyon::prelude::StrokeOptions;
+use bevy_prototype_lyon::prelude::shapes;
+
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+
+use crate::attach::AttachedTo;
+use crate::effect::Effect;
+use crate::effect::EffectKind;
+use crate::movement::Thrust;
+use crate::radar::ArcWidth;
+use crate::rotation::Heading;
+use crate::ship::Ship;
+use crate::time::Ticks;
+use crate::weapon::DebugWarhead;
+use crate::weapon::Shield;
+
+// Effect rendering:
+// - Every sim `Effect` gets a lyon shape that is rebuilt each tick from how far along it is, so
+//   it fades and grows with the sim ticks (frozen while paused, moves on a step).
+// - Beams are a thick fading line with a tracer running down it, blasts are an expanding ring,
+//   shield impacts flash the shield arc.
+// - Missiles get an exhaust flame that follows their main engine, rebuilt when the thrust changes.
+pub(super) struct EffectPlugin;
+impl Plugin for EffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                apply_effect_shape,
+                apply_exhaust_shape,
+                (
+                    // Nothing moves along between the ticks
+                    (update_effect_shape, update_tracer_shape).run_if(resource_changed::<Ticks>),
+                    update_exhaust_shape,
+                ),
+            )
+                .chain()
+                .before(bevy_prototype_lyon::plugin::BuildShapes),
+        );
+    }
+}
+
+// Above the ships
+const EFFECT_Z: f32 = 1.0;
+const BEAM_WIDTH: f32 = 40.0;
+const TRACER_LENGTH: f32 = 400.0;
+// Share of the beam life the tracer takes to reach the target
+const TRACER_SPAN: f32 = 0.3;
+const BLAST_WIDTH: f32 = 40.0;
+const SHIELD_FLASH_WIDTH: f32 = 30.0;
+// Points along the shield arc
+const SHIELD_FLASH_STEPS: u8 = 16;
+
+#[derive(Component)]
+struct EffectTracer;
+
+// Acceleration the flame was last built for
+#[derive(Component)]
+struct Exhaust(i32);
+
+fn stroke(color: Srgba, width: f32) -> Stroke {
+    Stroke {
+        options: StrokeOptions::default()
+            .with_line_width(width)
+            .with_line_cap(LineCap::Round),
+        color: color.into(),
+    }
+}
+
+fn line(from: Vec2, to: Vec2, color: Srgba, width: f32) -> Shape {
+    let path = ShapePath::new().move_to(from).line_to(to);
+    ShapeBuilder::with(&path)
+        .stroke(stroke(color, width))
+        .build()
+}
+
+fn beam_shape(origin: Vec2, target: Vec2, progress: f32) -> Shape {
+    let fade = 1.0 - progress;
+    line(
+        origin,
+        target,
+        css::RED.with_alpha(fade),
+        BEAM_WIDTH * fade + 4.0,
+    )
+}
+
+// Bright dash that runs from the shooter to the target early in the beam life
+fn tracer_shape(origin: Vec2, target: Vec2, progress: f32) -> Shape {
+    let travel = (progress / TRACER_SPAN).min(1.0);
+    let dash = TRACER_LENGTH / origin.distance(target).max(1.0);
+    let head = origin.lerp(target, travel);
+    let tail = origin.lerp(target, (travel - dash).max(0.0));
+    let alpha = if travel < 1.0 { 1.0 } else { 0.0 };
+    line(tail, head, css::WHITE.with_alpha(alpha), BEAM_WIDTH / 2.0)
+}
+
+fn blast_shape(origin: Vec2, radius: i64, progress: f32) -> Shape {
+    let fade = 1.0 - progress;
+    // Ease out, fast at first then slowing down as it reaches the edge
+    let circle = shapes::Circle {
+        radius: radius as f32 * (1.0 - fade * fade),
+        center: origin,
+    };
+    let color = css::ORANGE.mix(&css::RED, progress);
+    ShapeBuilder::with(&circle)
+        .fill(Fill::color(color.with_alpha(0.25 * fade)))
+        .stroke(stroke(color.with_alpha(fade), BLAST_WIDTH * fade + 5.0))
+        .build()
+}
+
+fn shield_flash_shape(
+    base: Vec2,
+    heading: Heading,
+    arc: ArcWidth,
+    radius: i64,
+    progress: f32,
+) -> Shape {
+    let fade = 1.0 - progress;
+    let radius = radius as f32;
+    let point = |step: u8| {
+        // Sweep from the ccw edge to the cw edge
+        let half_arc = f32::from(arc.current);
+        let offset = half_arc * (2.0 * f32::from(step) / f32::from(SHIELD_FLASH_STEPS) - 1.0);
+        let angle = (f32::from(heading.0.0) + offset) / 256.0 * std::f32::consts::TAU;
+        // Headings are clockwise from +Y
+        base + Vec2::new(angle.sin(), angle.cos()) * radius
+    };
+
+    let path = (1..=SHIELD_FLASH_STEPS).fold(ShapePath::new().move_to(point(0)), |path, step| {
+        path.line_to(point(step))
+    });
+    ShapeBuilder::with(&path)
+        .stroke(stroke(
+            css::AQUA.with_alpha(fade),
+            SHIELD_FLASH_WIDTH * fade + 5.0,
+        ))
+        .build()
+}
+
+// Flame behind a missile, grows with the engine thrust
+fn exhaust_shape(acceleration: i32) -> Shape {
+    let length = (acceleration as f32 * 2.0).clamp(20.0, 120.0);
+    let path = ShapePath::new()
+        .move_to(Vec2::new(-10.0, -30.0))
+        .line_to(Vec2::new(0.0, -30.0 - length))
+        .line_to(Vec2::new(10.0, -30.0))
+        .close();
+    ShapeBuilder::with(&path)
+        .fill(Fill::color(css::ORANGE.with_alpha(0.8)))
+        .stroke(stroke(css::YELLOW, 4.0))
+        .build()
+}
+
+// Shape for where the effect is at, None if there is nothing left to draw (shield is gone)
+fn effect_shape(
+    effect: &Effect,
+    ticks: &Ticks,
+    shield_query: &Query<(&Shield, &Heading, &ArcWidth, &AttachedTo)>,
+    parent_query: &Query<&Transform, With<Ship>>,
+) -> Option<Shape> {
+    let progress = effect.progress(ticks);
+    match effect.kind {
+        EffectKind::Beam { origin, target } => Some(beam_shape(origin, target, progress)),
+        EffectKind::Blast { origin, radius } => Some(blast_shape(origin, radius, progress)),
+        EffectKind::ShieldImpact { shield, .. } => {
+            let (shield, heading, arc, attached_to) = shield_query.get(shield).ok()?;
+            let tran = parent_query.get(attached_to.0).ok()?;
+            Some(shield_flash_shape(
+                tran.translation.truncate(),
+                *heading,
+                *arc,
+                shield.radius(),
+                progress,
+            ))
+        }
+    }
+}
+
+fn apply_effect_shape(
+    mut commands: Commands,
+    ticks: Res<Ticks>,
+    query: Query<(Entity, &Effect), Without<Shape>>,
+    shield_query: Query<(&Shield, &Heading, &ArcWidth, &AttachedTo)>,
+    parent_query: Query<&Transform, With<Ship>>,
+) {
+    for (entity, effect) in query.iter() {
+        let Some(shape) = effect_shape(effect, &ticks, &shield_query, &parent_query) else {
+            continue;
+        };
+
+        let mut entity = commands.entity(entity);
+        entity.insert((shape, Transform::from_xyz(0.0, 0.0, EFFECT_Z)));
+        if let EffectKind::Beam { origin, target } = effect.kind {
+            entity.with_child((
+                EffectTracer,
+                tracer_shape(origin, target, effect.progress(&ticks)),
+            ));
+        }
+    }
+}
+
+fn apply_exhaust_shape(mut commands: Commands, query: Query<Entity, Added<DebugWarhead>>) {
+    for entity in query.iter() {
+        commands.entity(entity).with_child((
+            Exhaust(0),
+            exhaust_shape(0),
+            Transform::from_xyz(0.0, 0.0, -0.1),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+fn update_effect_shape(
+    ticks: Res<Ticks>,
+    mut query: Query<(&Effect, &mut Shape), Without<EffectTracer>>,
+    shield_query: Query<(&Shield, &Heading, &ArcWidth, &AttachedTo)>,
+    parent_query: Query<&Transform, With<Ship>>,
+) {
+    for (effect, mut shape) in query.iter_mut() {
+        // Keeps the last look if the shield went away
+        if let Some(update) = effect_shape(effect, &ticks, &shield_query, &parent_query) {
+            *shape = update;
+        }
+    }
+}
+
+fn update_tracer_shape(
+    ticks: Res<Ticks>,
+    mut query: Query<(&ChildOf, &mut Shape), With<EffectTracer>>,
+    effect_query: Query<&Effect>,
+) {
+    for (child_of, mut shape) in query.iter_mut() {
+        if let Ok(effect) = effect_query.get(child_of.parent())
+            && let EffectKind::Beam { origin, target } = effect.kind
+        {
+            *shape = tracer_shape(origin, target, effect.progress(&ticks));
+        }
+    }
+}
+
+fn update_exhaust_shape(
+    mut query: Query<(&ChildOf, &mut Exhaust, &mut Shape, &mut Visibility)>,
+    thrust_query: Query<&Thrust>,
+) {
+    for (child_of, mut exhaust, mut shape, mut visibility) in query.iter_mut() {
+        // Engine can get shot off
+        let acceleration = thrust_query
+            .get(child_of.parent())
+            .map_or(0, |thrust| thrust.acceleration);
+        if acceleration == exhaust.0 {
+            continue;
+        }
+
+        exhaust.0 = acceleration;
+        if acceleration > 0 {
+            *shape = exhaust_shape(acceleration);
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
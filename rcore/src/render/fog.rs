@@ -0,0 +1,255 @@
+use avian2d::prelude::Position;
+use bevy::prelude::*;
+
+use std::collections::BTreeMap;
+
+use crate::attach::Attachments;
+use crate::radar::ContactMessage;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::style::faction_color;
+use crate::time::Ticks;
+
+use super::input::InputAction;
+use super::input::InputMap;
+
+// Fog of war:
+// - View mode that only shows what one faction perceives, its own ships, the contacts its radars
+//   reported and a ghost where the lost contacts were last seen. Everyone else is hidden.
+// - Built from the radar `ContactMessage` stream, the sightings are kept for every faction so
+//   switching the view doesn't start from nothing.
+// - A radar only reports its closest contact each tick, so a contact stays live for a few ticks
+//   before it turns into a ghost, then the ghost fades out.
+// - Hides the ships and their modules, the weapon effects are still drawn.
+pub(super) struct FogPlugin;
+impl Plugin for FogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FogOfWar>()
+            .init_gizmo_group::<FogGizmos>()
+            .add_systems(Startup, setup_fog_label)
+            .add_systems(
+                Update,
+                (
+                    fog_hotkeys,
+                    record_sightings,
+                    apply_fog_visibility,
+                    render_ghosts,
+                    render_fog_label,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Ticks a contact stays live without being reported again
+const LIVE_TICKS: u64 = 8;
+// Ticks the ghost takes to fade out, 5 seconds
+const GHOST_TICKS: u64 = 320;
+const GHOST_RADIUS: f32 = 300.0;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct FogGizmos;
+
+#[derive(Resource, Debug, Default)]
+pub(super) struct FogOfWar {
+    // Faction whose view is shown, None shows everything
+    pub faction: Option<Faction>,
+    // Last sighting by (seen by, contact), keyed by entity to keep a stable order
+    sightings: BTreeMap<(Faction, Entity), Sighting>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sighting {
+    pos: Vec2,
+    // Faction of the contact (decoys claim one too)
+    faction: Faction,
+    tick: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Seen {
+    Live,
+    // Ghost with its fade, 1.0 when just lost to 0.0 when gone
+    Ghost(f32),
+    Lost,
+}
+
+// How a sighting shows up after `elapsed` ticks
+fn seen(elapsed: u64) -> Seen {
+    if elapsed <= LIVE_TICKS {
+        Seen::Live
+    } else if elapsed < LIVE_TICKS + GHOST_TICKS {
+        Seen::Ghost(1.0 - (elapsed - LIVE_TICKS) as f32 / GHOST_TICKS as f32)
+    } else {
+        Seen::Lost
+    }
+}
+
+impl FogOfWar {
+    // Contact is currently seen by the viewed faction
+    fn live(&self, ticks: &Ticks, faction: Faction, entity: Entity) -> bool {
+        self.sightings
+            .get(&(faction, entity))
+            .is_some_and(|sighting| seen(ticks.elapsed(sighting.tick)) == Seen::Live)
+    }
+}
+
+// Hidden by the fog view, the trails, minimap, debug gizmos and inspector skip those too
+pub(super) fn fogged(visibility: Option<&Visibility>) -> bool {
+    visibility == Some(&Visibility::Hidden)
+}
+
+#[derive(Component)]
+struct FogLabel;
+
+fn setup_fog_label(mut commands: Commands) {
+    commands.spawn((
+        FogLabel,
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Px(18.),
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.),
+            bottom: Val::Px(50.),
+            ..default()
+        },
+    ));
+}
+
+// Cycles through the factions on the field, then back to seeing everything
+fn fog_hotkeys(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut fog: ResMut<FogOfWar>,
+    query: Query<&Faction, With<Ship>>,
+) {
+    if !input_map.just_pressed(InputAction::FogView, &key_input) {
+        return;
+    }
+    let mut factions: Vec<Faction> = query.iter().copied().collect();
+    factions.sort_unstable();
+    factions.dedup();
+
+    fog.faction = match fog.faction {
+        None => factions.first().copied(),
+        Some(current) => factions.into_iter().find(|faction| *faction > current),
+    };
+}
+
+fn record_sightings(
+    ticks: Res<Ticks>,
+    mut fog: ResMut<FogOfWar>,
+    mut messages: MessageReader<ContactMessage>,
+    faction_query: Query<&Faction>,
+    target_query: Query<(&Position, &Faction)>,
+) {
+    for ContactMessage(base, target) in messages.read() {
+        let Ok(faction) = faction_query.get(*base) else {
+            continue;
+        };
+        // Contact may be gone already
+        let Ok((pos, target_faction)) = target_query.get(*target) else {
+            continue;
+        };
+        fog.sightings.insert(
+            (*faction, *target),
+            Sighting {
+                pos: pos.0,
+                faction: *target_faction,
+                tick: ticks.now(),
+            },
+        );
+    }
+
+    fog.sightings
+        .retain(|_, sighting| seen(ticks.elapsed(sighting.tick)) != Seen::Lost);
+}
+
+fn apply_fog_visibility(
+    ticks: Res<Ticks>,
+    fog: Res<FogOfWar>,
+    ship_query: Query<(Entity, &Faction, Option<&Attachments>), With<Ship>>,
+    mut visibility_query: Query<&mut Visibility>,
+) {
+    for (entity, faction, attachments) in ship_query.iter() {
+        let shown = fog
+            .faction
+            .is_none_or(|view| view == *faction || fog.live(&ticks, view, entity));
+        let update = if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        let modules = attachments.into_iter().flat_map(RelationshipTarget::iter);
+        for entity in std::iter::once(entity).chain(modules) {
+            // Only touch it on changes
+            if let Ok(mut visibility) = visibility_query.get_mut(entity)
+                && *visibility != update
+            {
+                *visibility = update;
+            }
+        }
+    }
+}
+
+fn render_ghosts(
+    mut gizmos: Gizmos<FogGizmos>,
+    ticks: Res<Ticks>,
+    fog: Res<FogOfWar>,
+    ship_query: Query<(), With<Ship>>,
+) {
+    let Some(view) = fog.faction else {
+        return;
+    };
+
+    for ((faction, entity), sighting) in &fog.sightings {
+        if *faction != view {
+            continue;
+        }
+        let fade = match seen(ticks.elapsed(sighting.tick)) {
+            // Live ships are drawn as they are, decoys get a marker
+            Seen::Live if ship_query.contains(*entity) => continue,
+            Seen::Live => 1.0,
+            Seen::Ghost(fade) => fade,
+            Seen::Lost => continue,
+        };
+
+        let color = faction_color(sighting.faction).with_alpha(0.8 * fade);
+        let isometry = Isometry2d::from_translation(sighting.pos);
+        gizmos.circle_2d(isometry, GHOST_RADIUS, color);
+        gizmos.cross_2d(isometry, GHOST_RADIUS / 2.0, color);
+    }
+}
+
+fn render_fog_label(
+    fog: Res<FogOfWar>,
+    input_map: Res<InputMap>,
+    mut text: Single<&mut Text, With<FogLabel>>,
+) {
+    let label = fog.faction.map_or_else(String::new, |faction| {
+        let hint = input_map
+            .bindings(InputAction::FogView)
+            .next()
+            .map_or_else(String::new, |binding| format!(" [{}]", binding.name()));
+        format!("Fog of war - faction {}{hint}", faction.0)
+    });
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+#[test]
+fn test_seen() {
+    assert_eq!(seen(0), Seen::Live);
+    assert_eq!(seen(LIVE_TICKS), Seen::Live);
+    assert_eq!(
+        seen(LIVE_TICKS + 1),
+        Seen::Ghost(1.0 - 1.0 / GHOST_TICKS as f32)
+    );
+    assert_eq!(seen(LIVE_TICKS + GHOST_TICKS / 2), Seen::Ghost(0.5));
+    assert_eq!(seen(LIVE_TICKS + GHOST_TICKS), Seen::Lost);
+}
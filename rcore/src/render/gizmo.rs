@@ -8,6 +8,7 @@ use avian2d::prelude::LinearVelocity;
 use avian2d::prelude::Position;
 
 use crate::attach::AttachedTo;
+use crate::ship::Ship;
 
 use crate::rotation::Heading;
 use crate::rotation::RotDebug;
@@ -22,9 +23,13 @@ use crate::radar::ArcDebug;
 use crate::radar::ArcWidth;
 use crate::radar::RadarContact;
 use crate::radar::RadarDebug;
+use crate::radar::RadarRange;
 
+use crate::radar::signature_distance_squared;
 use crate::radar::within_radar;
 
+use super::fog::fogged;
+
 // Primitive bar-graph in gizmo form
 fn render_bar_gizmos(
     gizmos: &mut Gizmos,
@@ -50,10 +55,13 @@ fn render_bar_gizmos(
 }
 
 pub(super) fn movement(
-    query: Query<(&Transform, &LinearVelocity, &Thrust), With<MovDebug>>,
+    query: Query<(&Transform, &LinearVelocity, &Thrust, Option<&Visibility>), With<MovDebug>>,
     mut gizmos: Gizmos,
 ) {
-    for (tran, vel, thrust) in query.iter() {
+    for (tran, vel, thrust, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
         let base = tran.translation.truncate();
         let heading = tran.rotation;
         let velocity = vel.0;
@@ -89,13 +97,26 @@ pub(super) fn movement(
     }
 }
 
-#[expect(clippy::similar_names)]
+#[expect(clippy::similar_names, clippy::type_complexity)]
 pub(super) fn arc(
     mut gizmos: Gizmos,
-    query: Query<(&Heading, &TargetHeading, &ArcWidth, &AttachedTo), With<ArcDebug>>,
+    query: Query<
+        (
+            &Heading,
+            &TargetHeading,
+            &ArcWidth,
+            &AttachedTo,
+            Option<&Visibility>,
+        ),
+        With<ArcDebug>,
+    >,
     parent_query: Query<&Transform>,
 ) {
-    for (heading, target_heading, arc, attached_to) in query.iter() {
+    for (heading, target_heading, arc, attached_to, visibility) in query.iter() {
+        // Modules are fogged along with their ship
+        if fogged(visibility) {
+            continue;
+        }
         // Need the ship translation to position the arc gizmo right
         let base = parent_query
             .get(attached_to.0)
@@ -148,29 +169,43 @@ pub(super) fn arc(
     }
 }
 
+#[expect(clippy::type_complexity)]
 pub(super) fn radar(
     mut gizmos: Gizmos,
-    query: Query<(&Heading, &ArcWidth, &AttachedTo), With<RadarDebug>>,
-    parent_query: Query<(&Transform, &Position)>,
+    query: Query<
+        (
+            &Heading,
+            &ArcWidth,
+            &RadarRange,
+            &AttachedTo,
+            Option<&Visibility>,
+        ),
+        With<RadarDebug>,
+    >,
+    parent_query: Query<(&Transform, &Position, &Ship, Option<&Visibility>)>,
 ) {
-    for (heading, arc, attached_to) in query.iter() {
+    for (heading, arc, range, attached_to, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
         // Need the ship translation to position the radar gizmo right
         let (base, base_pos) = {
-            let (base, pos) = parent_query.get(attached_to.0).expect("attached");
+            let (base, pos, ..) = parent_query.get(attached_to.0).expect("attached");
             (base.translation.truncate(), pos)
         };
 
         // Draw distance & contact status
         gizmos.circle_2d(
             Isometry2d::from_translation(base),
-            crate::radar::DISTANCE as f32,
+            range.0 as f32,
             bevy::color::palettes::css::GREEN,
         );
 
         // Draw line between this ship (owner of this radar) and all target
         // color the target if they register as an contact (on radar)
-        for (target_base, target_pos) in parent_query.iter() {
-            if base_pos.0 == target_pos.0 {
+        for (target_base, target_pos, target_class, target_visibility) in parent_query.iter() {
+            // Lines to the hidden ships would give them away
+            if base_pos.0 == target_pos.0 || fogged(target_visibility) {
                 continue;
             }
 
@@ -180,7 +215,7 @@ pub(super) fn radar(
                 target_pos.0.as_ivec2(),
                 heading.0,
                 arc.current,
-                crate::radar::DISTANCE_SQUARED,
+                signature_distance_squared(range.0, target_class.0.stats().radar_signature),
             ) {
                 RadarContact::Contact => bevy::color::palettes::css::GREEN,
                 RadarContact::OutsideArc => bevy::color::palettes::css::YELLOW,
@@ -196,9 +231,12 @@ pub(super) fn radar(
 #[expect(clippy::similar_names)]
 pub(super) fn rotation(
     mut gizmos: Gizmos,
-    query: Query<(&Transform, &TargetHeading), With<RotDebug>>,
+    query: Query<(&Transform, &TargetHeading, Option<&Visibility>), With<RotDebug>>,
 ) {
-    for (tran, target) in query.iter() {
+    for (tran, target, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
         let base = tran.translation.truncate();
         let heading = tran.rotation;
         let qtarget = target.target.to_quat();
@@ -256,9 +294,12 @@ pub(super) fn rotation(
 #[expect(clippy::type_complexity)]
 pub(super) fn health(
     mut gizmos: Gizmos,
-    query: Query<(&Health, &Transform), (With<HealthDebug>, Without<Shield>)>,
+    query: Query<(&Health, &Transform, Option<&Visibility>), (With<HealthDebug>, Without<Shield>)>,
 ) {
-    for (health, tran) in query.iter() {
+    for (health, tran, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
         let base = tran.translation.truncate();
 
         render_bar_gizmos(
@@ -274,10 +315,16 @@ pub(super) fn health(
 #[expect(clippy::type_complexity)]
 pub(super) fn shield_health(
     mut gizmos: Gizmos,
-    query: Query<(&Health, &AttachedTo), (With<ShieldHealthDebug>, With<Shield>)>,
+    query: Query<
+        (&Health, &AttachedTo, Option<&Visibility>),
+        (With<ShieldHealthDebug>, With<Shield>),
+    >,
     parent_query: Query<&Transform>,
 ) {
-    for (health, attached_to) in query.iter() {
+    for (health, attached_to, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
         let base = parent_query
             .get(attached_to.0)
             .expect("attached")
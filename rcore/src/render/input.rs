@@ -0,0 +1,484 @@
+use bevy::prelude::*;
+
+use std::path::PathBuf;
+
+use super::debug_overlay::DebugToggle;
+
+// Input bindings:
+// - Every keyboard control goes through the `InputMap` actions instead of hard-coded keys, the
+//   defaults can be overridden from a config file (`InputConfig`) at startup.
+// - Config format is one action per line, `action = Binding, Binding`, a binding is a bevy
+//   `KeyCode` name with an optional `Ctrl+`, `Shift+` or `Alt+` modifier, `#` starts a comment.
+// - An action listed in the config replaces all of its default bindings, the rest keep theirs.
+// - A binding without a modifier won't fire while a modifier is held, so `Digit1` and
+//   `Ctrl+Digit1` can be bound to different actions.
+pub(super) struct InputPlugin;
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .add_systems(PreStartup, load_input_config);
+    }
+}
+
+// Camera bookmark slots
+pub const BOOKMARKS: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    // Camera
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    ZoomIn,
+    ZoomOut,
+    CameraAuto,
+    // Follow target cycling, in entity order
+    FollowNext,
+    FollowPrevious,
+    // First ship of the next faction
+    FollowFaction,
+    // Bookmark slot (0 based)
+    BookmarkSave(u8),
+    BookmarkRecall(u8),
+    // Time controls
+    Pause,
+    SlowDown,
+    SpeedUp,
+    Step,
+    // Write a snapshot of the sim (needs a `SnapshotExport`)
+    Snapshot,
+    // Motion trails and predicted paths on/off
+    ToggleTrails,
+    TogglePrediction,
+    // Fog of war view, cycles through the factions
+    FogView,
+    // All of the debug gizmos on/off
+    ToggleGizmos,
+    // Debug markers for the ships in scope
+    ToggleDebug(DebugToggle),
+    // Selected ship or all ships
+    DebugScope,
+    DebugPanel,
+}
+
+impl InputAction {
+    fn all() -> impl Iterator<Item = Self> {
+        [
+            Self::PanUp,
+            Self::PanDown,
+            Self::PanLeft,
+            Self::PanRight,
+            Self::ZoomIn,
+            Self::ZoomOut,
+            Self::CameraAuto,
+            Self::FollowNext,
+            Self::FollowPrevious,
+            Self::FollowFaction,
+        ]
+        .into_iter()
+        .chain((0..BOOKMARKS).map(Self::BookmarkSave))
+        .chain((0..BOOKMARKS).map(Self::BookmarkRecall))
+        .chain([
+            Self::Pause,
+            Self::SlowDown,
+            Self::SpeedUp,
+            Self::Step,
+            Self::Snapshot,
+            Self::ToggleTrails,
+            Self::TogglePrediction,
+            Self::FogView,
+            Self::ToggleGizmos,
+        ])
+        .chain(DebugToggle::ALL.map(Self::ToggleDebug))
+        .chain([Self::DebugScope, Self::DebugPanel])
+    }
+
+    fn name(self) -> String {
+        match self {
+            Self::PanUp => "pan_up".to_owned(),
+            Self::PanDown => "pan_down".to_owned(),
+            Self::PanLeft => "pan_left".to_owned(),
+            Self::PanRight => "pan_right".to_owned(),
+            Self::ZoomIn => "zoom_in".to_owned(),
+            Self::ZoomOut => "zoom_out".to_owned(),
+            Self::CameraAuto => "camera_auto".to_owned(),
+            Self::FollowNext => "follow_next".to_owned(),
+            Self::FollowPrevious => "follow_previous".to_owned(),
+            Self::FollowFaction => "follow_faction".to_owned(),
+            // Slots are 1 based in the config, same as the default keys
+            Self::BookmarkSave(slot) => format!("bookmark_save_{}", slot + 1),
+            Self::BookmarkRecall(slot) => format!("bookmark_recall_{}", slot + 1),
+            Self::Pause => "pause".to_owned(),
+            Self::SlowDown => "slow_down".to_owned(),
+            Self::SpeedUp => "speed_up".to_owned(),
+            Self::Step => "step".to_owned(),
+            Self::Snapshot => "snapshot".to_owned(),
+            Self::ToggleTrails => "toggle_trails".to_owned(),
+            Self::TogglePrediction => "toggle_prediction".to_owned(),
+            Self::FogView => "fog_view".to_owned(),
+            Self::ToggleGizmos => "toggle_gizmos".to_owned(),
+            Self::ToggleDebug(toggle) => format!("debug_{}", toggle.name()),
+            Self::DebugScope => "debug_scope".to_owned(),
+            Self::DebugPanel => "debug_panel".to_owned(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::all().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+}
+
+impl Modifier {
+    const ALL: [(Self, &str, [KeyCode; 2]); 3] = [
+        (
+            Self::Ctrl,
+            "Ctrl",
+            [KeyCode::ControlLeft, KeyCode::ControlRight],
+        ),
+        (
+            Self::Shift,
+            "Shift",
+            [KeyCode::ShiftLeft, KeyCode::ShiftRight],
+        ),
+        (Self::Alt, "Alt", [KeyCode::AltLeft, KeyCode::AltRight]),
+    ];
+
+    fn held(keys: &ButtonInput<KeyCode>) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|(_, _, codes)| keys.any_pressed(*codes))
+            .map(|(modifier, ..)| modifier)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub key: KeyCode,
+    pub modifier: Option<Modifier>,
+}
+
+impl Binding {
+    const fn key(key: KeyCode) -> Self {
+        Self {
+            key,
+            modifier: None,
+        }
+    }
+
+    const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            key,
+            modifier: Some(Modifier::Ctrl),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let (modifier, key) = match text.split_once('+') {
+            Some((modifier, key)) => {
+                let modifier = Modifier::ALL
+                    .into_iter()
+                    .find(|(_, name, _)| *name == modifier.trim())
+                    .map(|(modifier, ..)| modifier)
+                    .ok_or_else(|| format!("unknown modifier `{}`", modifier.trim()))?;
+                (Some(modifier), key.trim())
+            }
+            None => (None, text),
+        };
+        let key = KEY_NAMES
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, key)| *key)
+            .ok_or_else(|| format!("unknown key `{key}`"))?;
+        Ok(Self { key, modifier })
+    }
+
+    pub fn name(self) -> String {
+        let key = KEY_NAMES
+            .iter()
+            .find(|(_, key)| *key == self.key)
+            .map_or("?", |(name, _)| name);
+        match self.modifier {
+            Some(modifier) => {
+                let (_, modifier, _) = Modifier::ALL
+                    .into_iter()
+                    .find(|(other, ..)| *other == modifier)
+                    .expect("modifier");
+                format!("{modifier}+{key}")
+            }
+            None => key.to_owned(),
+        }
+    }
+}
+
+// Where to load the input config from
+#[derive(Resource, Debug, Clone)]
+pub struct InputConfig(pub PathBuf);
+
+#[derive(Resource, Debug, Clone)]
+pub struct InputMap {
+    bindings: Vec<(InputAction, Binding)>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let mut bindings = vec![
+            (InputAction::PanUp, Binding::key(KeyCode::KeyW)),
+            (InputAction::PanUp, Binding::key(KeyCode::ArrowUp)),
+            (InputAction::PanDown, Binding::key(KeyCode::KeyS)),
+            (InputAction::PanDown, Binding::key(KeyCode::ArrowDown)),
+            (InputAction::PanLeft, Binding::key(KeyCode::KeyA)),
+            (InputAction::PanLeft, Binding::key(KeyCode::ArrowLeft)),
+            (InputAction::PanRight, Binding::key(KeyCode::KeyD)),
+            (InputAction::PanRight, Binding::key(KeyCode::ArrowRight)),
+            (InputAction::ZoomIn, Binding::key(KeyCode::Equal)),
+            (InputAction::ZoomOut, Binding::key(KeyCode::Minus)),
+            (InputAction::CameraAuto, Binding::key(KeyCode::Home)),
+            (InputAction::FollowNext, Binding::key(KeyCode::Tab)),
+            (InputAction::FollowPrevious, Binding::ctrl(KeyCode::Tab)),
+            (InputAction::FollowFaction, Binding::key(KeyCode::KeyF)),
+            (InputAction::Pause, Binding::key(KeyCode::Space)),
+            (InputAction::SlowDown, Binding::key(KeyCode::BracketLeft)),
+            (InputAction::SpeedUp, Binding::key(KeyCode::BracketRight)),
+            (InputAction::Step, Binding::key(KeyCode::Period)),
+            (InputAction::Snapshot, Binding::key(KeyCode::F12)),
+            (InputAction::ToggleTrails, Binding::key(KeyCode::KeyT)),
+            (InputAction::TogglePrediction, Binding::key(KeyCode::KeyP)),
+            (InputAction::FogView, Binding::key(KeyCode::KeyV)),
+            (InputAction::ToggleGizmos, Binding::key(KeyCode::KeyG)),
+        ];
+
+        // Ctrl+1 saves the camera into slot 1, 1 jumps back to it
+        let digits = [
+            KeyCode::Digit1,
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit4,
+        ];
+        for (slot, key) in (0..BOOKMARKS).zip(digits) {
+            bindings.push((InputAction::BookmarkSave(slot), Binding::ctrl(key)));
+            bindings.push((InputAction::BookmarkRecall(slot), Binding::key(key)));
+        }
+
+        // Debug markers on F1 to F7
+        let function_keys = [
+            KeyCode::F1,
+            KeyCode::F2,
+            KeyCode::F3,
+            KeyCode::F4,
+            KeyCode::F5,
+            KeyCode::F6,
+            KeyCode::F7,
+        ];
+        for (toggle, key) in DebugToggle::ALL.into_iter().zip(function_keys) {
+            bindings.push((InputAction::ToggleDebug(toggle), Binding::key(key)));
+        }
+        bindings.push((InputAction::DebugScope, Binding::key(KeyCode::F8)));
+        bindings.push((InputAction::DebugPanel, Binding::key(KeyCode::F9)));
+
+        Self { bindings }
+    }
+}
+
+impl InputMap {
+    // Defaults with the config overrides applied
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut map = Self::default();
+        for (number, line) in config.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |err: String| format!("line {}: {err}", number + 1);
+            let (name, bindings) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `action = Binding`".to_owned()))?;
+            let action = InputAction::from_name(name.trim())
+                .ok_or_else(|| error(format!("unknown action `{}`", name.trim())))?;
+
+            map.bindings.retain(|(other, _)| *other != action);
+            for binding in bindings.split(',').map(str::trim) {
+                // Empty list unbinds the action
+                if binding.is_empty() {
+                    continue;
+                }
+                let binding = Binding::parse(binding).map_err(error)?;
+                map.bindings.push((action, binding));
+            }
+        }
+        Ok(map)
+    }
+
+    // Config file with every binding in it
+    pub fn to_config(&self) -> String {
+        InputAction::all()
+            .map(|action| {
+                let bindings: Vec<String> = self.bindings(action).map(Binding::name).collect();
+                format!("{} = {}", action.name(), bindings.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn bindings(&self, action: InputAction) -> impl Iterator<Item = Binding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(other, _)| *other == action)
+            .map(|(_, binding)| *binding)
+    }
+
+    // Any of the action keys is held down
+    pub fn pressed(&self, action: InputAction, keys: &ButtonInput<KeyCode>) -> bool {
+        let modifier = Modifier::held(keys);
+        self.bindings(action)
+            .any(|binding| binding.modifier == modifier && keys.pressed(binding.key))
+    }
+
+    // Any of the action keys went down this frame
+    pub fn just_pressed(&self, action: InputAction, keys: &ButtonInput<KeyCode>) -> bool {
+        let modifier = Modifier::held(keys);
+        self.bindings(action)
+            .any(|binding| binding.modifier == modifier && keys.just_pressed(binding.key))
+    }
+}
+
+fn load_input_config(config: Option<Res<InputConfig>>, mut input_map: ResMut<InputMap>) {
+    let Some(config) = config else {
+        return;
+    };
+    // No config file is fine, the defaults are there
+    let Ok(text) = std::fs::read_to_string(&config.0) else {
+        return;
+    };
+    match InputMap::parse(&text) {
+        Ok(map) => *input_map = map,
+        Err(err) => println!("ERROR - INPUT - {}: {err}", config.0.display()),
+    }
+}
+
+// Config names for the keys, same as the bevy `KeyCode` names
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("KeyA", KeyCode::KeyA),
+    ("KeyB", KeyCode::KeyB),
+    ("KeyC", KeyCode::KeyC),
+    ("KeyD", KeyCode::KeyD),
+    ("KeyE", KeyCode::KeyE),
+    ("KeyF", KeyCode::KeyF),
+    ("KeyG", KeyCode::KeyG),
+    ("KeyH", KeyCode::KeyH),
+    ("KeyI", KeyCode::KeyI),
+    ("KeyJ", KeyCode::KeyJ),
+    ("KeyK", KeyCode::KeyK),
+    ("KeyL", KeyCode::KeyL),
+    ("KeyM", KeyCode::KeyM),
+    ("KeyN", KeyCode::KeyN),
+    ("KeyO", KeyCode::KeyO),
+    ("KeyP", KeyCode::KeyP),
+    ("KeyQ", KeyCode::KeyQ),
+    ("KeyR", KeyCode::KeyR),
+    ("KeyS", KeyCode::KeyS),
+    ("KeyT", KeyCode::KeyT),
+    ("KeyU", KeyCode::KeyU),
+    ("KeyV", KeyCode::KeyV),
+    ("KeyW", KeyCode::KeyW),
+    ("KeyX", KeyCode::KeyX),
+    ("KeyY", KeyCode::KeyY),
+    ("KeyZ", KeyCode::KeyZ),
+    ("Digit0", KeyCode::Digit0),
+    ("Digit1", KeyCode::Digit1),
+    ("Digit2", KeyCode::Digit2),
+    ("Digit3", KeyCode::Digit3),
+    ("Digit4", KeyCode::Digit4),
+    ("Digit5", KeyCode::Digit5),
+    ("Digit6", KeyCode::Digit6),
+    ("Digit7", KeyCode::Digit7),
+    ("Digit8", KeyCode::Digit8),
+    ("Digit9", KeyCode::Digit9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("ArrowUp", KeyCode::ArrowUp),
+    ("ArrowDown", KeyCode::ArrowDown),
+    ("ArrowLeft", KeyCode::ArrowLeft),
+    ("ArrowRight", KeyCode::ArrowRight),
+    ("Space", KeyCode::Space),
+    ("Tab", KeyCode::Tab),
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Minus", KeyCode::Minus),
+    ("Equal", KeyCode::Equal),
+    ("BracketLeft", KeyCode::BracketLeft),
+    ("BracketRight", KeyCode::BracketRight),
+    ("Backslash", KeyCode::Backslash),
+    ("Semicolon", KeyCode::Semicolon),
+    ("Quote", KeyCode::Quote),
+    ("Backquote", KeyCode::Backquote),
+    ("Comma", KeyCode::Comma),
+    ("Period", KeyCode::Period),
+    ("Slash", KeyCode::Slash),
+];
+
+#[test]
+fn test_input_map_parse() {
+    let map = InputMap::parse(
+        "# Arrows only\n\
+         pan_up = ArrowUp\n\
+         bookmark_save_2 = Shift+F2, Ctrl+Digit2 # two ways\n\
+         step =\n",
+    )
+    .expect("config");
+
+    assert_eq!(
+        map.bindings(InputAction::PanUp).collect::<Vec<_>>(),
+        vec![Binding::key(KeyCode::ArrowUp)]
+    );
+    assert_eq!(map.bindings(InputAction::Step).count(), 0);
+    assert_eq!(
+        map.bindings(InputAction::BookmarkSave(1))
+            .map(Binding::name)
+            .collect::<Vec<_>>(),
+        vec!["Shift+F2", "Ctrl+Digit2"]
+    );
+    // Untouched
+    assert_eq!(map.bindings(InputAction::PanDown).count(), 2);
+
+    // Defaults round trip
+    let map = InputMap::parse(&InputMap::default().to_config()).expect("config");
+    assert_eq!(map.to_config(), InputMap::default().to_config());
+
+    // Shipped config matches the defaults
+    let map = InputMap::parse(include_str!("../../input.cfg")).expect("config");
+    assert_eq!(map.to_config(), InputMap::default().to_config());
+
+    assert_eq!(
+        InputMap::parse("warp = KeyW").expect_err("unknown action"),
+        "line 1: unknown action `warp`"
+    );
+    assert_eq!(
+        InputMap::parse("\npan_up = Meta+KeyW").expect_err("unknown modifier"),
+        "line 2: unknown modifier `Meta`"
+    );
+}
@@ -0,0 +1,349 @@
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::attach::Attachments;
+use crate::autopilot::Autopilot;
+use crate::module::Module;
+use crate::movement::Thrust;
+use crate::radar::ArcWidth;
+use crate::radar::Radar;
+use crate::radar::RadarRange;
+use crate::rotation::Heading;
+use crate::rotation::TargetHeading;
+use crate::script::LastAction;
+use crate::script::ShipAction;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::weapon::DebugMissile;
+use crate::weapon::DebugWeapon;
+use crate::weapon::Health;
+use crate::weapon::Shield;
+
+use super::camera::CameraMode;
+use super::camera::CameraRig;
+use super::fog::fogged;
+use super::pointer_over_ui;
+
+// Ship inspector:
+// - Left click on a ship selects it, the panel shows its state live till it's closed (Escape or
+//   the close button) or the ship is gone.
+// - The follow button hands the ship to the camera rig.
+pub(super) struct InspectorPlugin;
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selected>()
+            .add_systems(Startup, setup_inspector)
+            .add_systems(
+                Update,
+                (
+                    select_ship,
+                    inspector_buttons,
+                    render_inspector,
+                    render_selected,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Ship picked out by the player
+#[derive(Resource, Debug, Default)]
+pub struct Selected(pub Option<Entity>);
+
+#[derive(Component)]
+struct InspectorPanel;
+
+#[derive(Component)]
+struct InspectorText;
+
+#[derive(Component)]
+enum InspectorButton {
+    Follow,
+    Close,
+}
+
+fn setup_inspector(mut commands: Commands) {
+    let button = |label: &str, action: InspectorButton| {
+        (
+            Button,
+            action,
+            Node {
+                padding: UiRect::axes(Val::Px(10.), Val::Px(4.)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            children![(
+                Text::new(label),
+                TextFont {
+                    font_size: FontSize::Px(16.),
+                    ..default()
+                },
+            )],
+        )
+    };
+
+    commands.spawn((
+        InspectorPanel,
+        // Block the clicks from reaching the camera
+        Interaction::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(20.),
+            top: Val::Px(20.),
+            width: Val::Px(320.),
+            padding: UiRect::all(Val::Px(10.)),
+            row_gap: Val::Px(8.),
+            flex_direction: FlexDirection::Column,
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.05, 0.05, 0.85)),
+        children![
+            (
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.),
+                    ..default()
+                },
+                InspectorText,
+            ),
+            (
+                Node {
+                    column_gap: Val::Px(8.),
+                    ..default()
+                },
+                children![
+                    button("Follow", InspectorButton::Follow),
+                    button("Close", InspectorButton::Close),
+                ],
+            ),
+        ],
+    ));
+}
+
+// Pick the ship under the cursor, closest hull center wins on overlaps
+fn select_ship(
+    mut selected: ResMut<Selected>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<CameraRig>>,
+    interactions: Query<&Interaction>,
+    query: Query<(Entity, &Ship, &Transform, Option<&Visibility>)>,
+) {
+    if key_input.just_pressed(KeyCode::Escape) {
+        selected.0 = None;
+    }
+    if !mouse_input.just_pressed(MouseButton::Left) || pointer_over_ui(&interactions) {
+        return;
+    }
+
+    let (cam, cam_tran) = camera.into_inner();
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| cam.viewport_to_world_2d(cam_tran, cursor).ok())
+    else {
+        return;
+    };
+
+    // Missing the ships keeps the selection, the click was likely a drag-pan
+    // Can't pick out the ships hidden by the fog view
+    let hit = query
+        .iter()
+        .filter(|(.., visibility)| !fogged(*visibility))
+        .map(|(entity, ship, tran, _)| {
+            let distance = tran.translation.truncate().distance(cursor);
+            (entity, distance, ship.0.stats().collider_radius)
+        })
+        .filter(|(_, distance, radius)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    if let Some((entity, ..)) = hit {
+        selected.0 = Some(entity);
+    }
+}
+
+fn inspector_buttons(
+    mut selected: ResMut<Selected>,
+    mut rig: Single<&mut CameraRig>,
+    query: Query<(&Interaction, &InspectorButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            InspectorButton::Follow => {
+                if let Some(entity) = selected.0 {
+                    rig.mode = CameraMode::Follow(entity);
+                }
+            }
+            InspectorButton::Close => selected.0 = None,
+        }
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn render_inspector(
+    mut selected: ResMut<Selected>,
+    mut panel: Single<&mut Node, With<InspectorPanel>>,
+    mut text: Single<&mut Text, With<InspectorText>>,
+    ship_query: Query<(
+        &Ship,
+        &Faction,
+        &Health,
+        &LinearVelocity,
+        Option<&Thrust>,
+        &Heading,
+        &TargetHeading,
+        Option<&DebugWeapon>,
+        Option<&DebugMissile>,
+        Option<&Autopilot>,
+        Option<&Attachments>,
+        Option<&LastAction>,
+        Option<&Visibility>,
+    )>,
+    shield_query: Query<(&Health, &ArcWidth, &Heading), With<Shield>>,
+    radar_query: Query<(&ArcWidth, &RadarRange, &Heading), With<Radar>>,
+    module_query: Query<&Module>,
+) {
+    let Some(entity) = selected.0 else {
+        panel.display = Display::None;
+        return;
+    };
+    let Ok((
+        ship,
+        faction,
+        health,
+        velocity,
+        thrust,
+        heading,
+        target,
+        weapon,
+        missile,
+        autopilot,
+        attachments,
+        action,
+        visibility,
+    )) = ship_query.get(entity)
+    else {
+        // Destroyed or expended
+        selected.0 = None;
+        panel.display = Display::None;
+        return;
+    };
+    // Keep the selection, it shows up again once the ship is seen
+    if fogged(visibility) {
+        panel.display = Display::None;
+        return;
+    }
+    panel.display = Display::Flex;
+
+    let mut lines = vec![
+        format!("{:?} - {entity} - Faction {}", ship.0, faction.0),
+        format!("Health: {}/{}", health.current, health.maximum),
+        format!("Velocity: ({:.0}, {:.0})", velocity.0.x, velocity.0.y),
+        match thrust {
+            Some(thrust) => format!(
+                "Thrust: {} (limit {})",
+                thrust.acceleration, thrust.velocity_limit
+            ),
+            None => "Thrust: engine lost".to_owned(),
+        },
+        format!(
+            "Heading: {} -> {} ({}/s)",
+            heading.0.0, target.target.0, target.limit
+        ),
+    ];
+
+    if let Some(autopilot) = autopilot.and_then(|autopilot| autopilot.order) {
+        lines.push(format!("Autopilot: {autopilot:?}"));
+    }
+    if let Some(weapon) = weapon {
+        lines.push(format!(
+            "Weapon: {}/{} cooldown, {} dmg",
+            weapon.current, weapon.cooldown, weapon.damage
+        ));
+    }
+    if let Some(missile) = missile {
+        lines.push(format!(
+            "Missile: {}/{} cooldown",
+            missile.current, missile.cooldown
+        ));
+    }
+
+    for attachment in attachments
+        .iter()
+        .flat_map(|attachments| attachments.iter())
+    {
+        if let Ok((health, arc, heading)) = shield_query.get(attachment) {
+            lines.push(format!(
+                "Shield: {}/{} - arc {} at {}",
+                health.current, health.maximum, arc.current, heading.0.0
+            ));
+        }
+        if let Ok((arc, range, heading)) = radar_query.get(attachment) {
+            lines.push(format!(
+                "Radar: arc {} at {} - range {}",
+                arc.current, heading.0.0, range.0
+            ));
+        }
+        if let Ok(module) = module_query.get(attachment) {
+            lines.push(format!(
+                "  {:?}: {}/{}",
+                module.kind, module.current, module.maximum
+            ));
+        }
+    }
+
+    if let Some(LastAction(action)) = action {
+        lines.push(action_text(action));
+    }
+
+    text.0 = lines.join("\n");
+}
+
+fn action_text(action: &ShipAction) -> String {
+    let mut parts = vec![
+        format!("heading {:+}", action.heading.0),
+        format!("accel {}", action.acceleration),
+        format!("rcs ({}, {})", action.rcs.x, action.rcs.y),
+        format!("radar {:+}", action.radar_heading.0),
+    ];
+    if let Some(target) = action.target_entity {
+        parts.push(format!("target {target}"));
+    }
+    if let Some(order) = action.autopilot {
+        parts.push(format!("autopilot {order:?}"));
+    }
+    if action.sleep.is_some() {
+        parts.push("sleep".to_owned());
+    }
+    if let Some(active) = action.jammer {
+        parts.push(format!("jammer {active}"));
+    }
+    if action.decoy.is_some() {
+        parts.push("decoy".to_owned());
+    }
+    if let Some((target, amount)) = action.transfer {
+        parts.push(format!("transfer {amount} to {target}"));
+    }
+    format!("Last action: {}", parts.join(", "))
+}
+
+fn render_selected(
+    mut gizmos: Gizmos,
+    selected: Res<Selected>,
+    query: Query<(&Ship, &Transform, Option<&Visibility>)>,
+) {
+    if let Some((ship, tran, visibility)) = selected.0.and_then(|entity| query.get(entity).ok())
+        && !fogged(visibility)
+    {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(tran.translation.truncate()),
+            ship.0.stats().collider_radius * 1.5,
+            bevy::color::palettes::css::YELLOW,
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use bevy::camera::ScalingMode;
+use bevy::camera::Viewport;
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::ARENA_SIZE;
+use crate::attach::AttachedTo;
+use crate::math::AbsRot;
+use crate::radar::ArcWidth;
+use crate::radar::Radar;
+use crate::radar::RadarRange;
+use crate::rotation::Heading;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::style::faction_color;
+
+use super::camera::CameraRig;
+use super::fog::fogged;
+
+// Minimap:
+// - A second camera renders the whole arena into a corner viewport, it only sees the minimap
+//   gizmo layer so everything is drawn in world coordinates and the camera does the scaling.
+// - Ships are dots (faction color, sized by class), radars are cones, the main camera view is a
+//   rectangle.
+// - Clicking (or dragging) on the minimap re-centers the camera rig there in free mode.
+pub(super) struct MinimapPlugin;
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_gizmo_config(
+            MinimapGizmos,
+            GizmoConfig {
+                render_layers: RenderLayers::layer(MINIMAP_LAYER),
+                ..default()
+            },
+        )
+        .add_systems(Startup, setup_minimap)
+        .add_systems(
+            Update,
+            (
+                minimap_viewport,
+                minimap_click,
+                render_minimap_ships,
+                render_minimap_radars,
+                render_minimap_view,
+            ),
+        );
+    }
+}
+
+const MINIMAP_LAYER: usize = 1;
+// Logical pixels, same aspect as the arena
+const MINIMAP_SIZE: Vec2 = Vec2::new(320., 200.);
+const MINIMAP_MARGIN: f32 = 20.;
+// Room around the arena bounds
+const MINIMAP_PADDING: f32 = 1.05;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct MinimapGizmos;
+
+#[derive(Component)]
+struct MinimapCamera;
+
+#[derive(Component)]
+struct MinimapPanel;
+
+fn setup_minimap(mut commands: Commands) {
+    let extent = ARENA_SIZE * MINIMAP_PADDING;
+    commands.spawn((
+        MinimapCamera,
+        Camera2d,
+        Camera {
+            // Draw over the main camera
+            order: 1,
+            clear_color: ClearColorConfig::Custom(Color::srgba(0.05, 0.05, 0.05, 0.85)),
+            ..default()
+        },
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: extent.x,
+                height: extent.y,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+        RenderLayers::layer(MINIMAP_LAYER),
+    ));
+
+    // Same spot as the viewport, takes the clicks and keeps them from reaching the world
+    commands.spawn((
+        MinimapPanel,
+        Interaction::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(MINIMAP_MARGIN),
+            bottom: Val::Px(MINIMAP_MARGIN),
+            width: Val::Px(MINIMAP_SIZE.x),
+            height: Val::Px(MINIMAP_SIZE.y),
+            border: UiRect::all(Val::Px(1.)),
+            ..default()
+        },
+        BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+    ));
+}
+
+// Minimap area of the window in logical pixels, top-left origin
+fn minimap_rect(window: &Window) -> Rect {
+    let max = window.size() - Vec2::splat(MINIMAP_MARGIN);
+    Rect::from_corners(max - MINIMAP_SIZE, max)
+}
+
+// Cursor on the minimap to the world position under it
+fn minimap_to_world(rect: Rect, cursor: Vec2) -> Vec2 {
+    let extent = ARENA_SIZE * MINIMAP_PADDING;
+    let ratio = (cursor - rect.center()) / rect.size();
+    Vec2::new(ratio.x, -ratio.y) * extent
+}
+
+// Keep the viewport glued to the corner as the window resizes
+fn minimap_viewport(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut camera: Single<&mut Camera, With<MinimapCamera>>,
+) {
+    let rect = minimap_rect(&window);
+    let scale = window.scale_factor();
+    let physical_position = (rect.min * scale).max(Vec2::ZERO).as_uvec2();
+    let physical_size = (rect.size() * scale).as_uvec2();
+
+    // Window is too small to fit it
+    camera.is_active = rect.min.x >= 0. && rect.min.y >= 0.;
+    if camera.is_active
+        && camera.viewport.as_ref().is_none_or(|viewport| {
+            viewport.physical_position != physical_position
+                || viewport.physical_size != physical_size
+        })
+    {
+        camera.viewport = Some(Viewport {
+            physical_position,
+            physical_size,
+            ..default()
+        });
+    }
+}
+
+fn minimap_click(
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut rig: Single<&mut CameraRig>,
+    panel: Single<&Interaction, With<MinimapPanel>>,
+) {
+    if **panel != Interaction::Pressed {
+        return;
+    }
+    let rect = minimap_rect(&window);
+    if let Some(cursor) = window
+        .cursor_position()
+        .filter(|cursor| rect.contains(*cursor))
+    {
+        rig.recenter(minimap_to_world(rect, cursor));
+    }
+}
+
+// Dot size in rings, a ring is about a pixel on the minimap
+const fn dot_rings(class: ShipClass) -> u8 {
+    match class {
+        ShipClass::Large => 4,
+        ShipClass::Medium => 3,
+        ShipClass::Small => 2,
+        ShipClass::Tiny => 1,
+    }
+}
+
+fn render_minimap_ships(
+    mut gizmos: Gizmos<MinimapGizmos>,
+    query: Query<(&Ship, &Faction, &Transform, Option<&Visibility>)>,
+) {
+    gizmos.rect_2d(
+        Isometry2d::IDENTITY,
+        ARENA_SIZE,
+        bevy::color::palettes::css::RED,
+    );
+
+    for (ship, faction, tran, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
+        let isometry = Isometry2d::from_translation(tran.translation.truncate());
+        let color = faction_color(*faction);
+
+        // Stack circles to fill the dot in
+        for ring in 1..=dot_rings(ship.0) {
+            gizmos.circle_2d(isometry, f32::from(ring) * 30., color);
+        }
+    }
+}
+
+fn render_minimap_radars(
+    mut gizmos: Gizmos<MinimapGizmos>,
+    query: Query<(&Heading, &ArcWidth, &RadarRange, &AttachedTo), With<Radar>>,
+    parent_query: Query<(&Transform, &Faction, Option<&Visibility>)>,
+) {
+    for (heading, arc, range, attached_to) in query.iter() {
+        let Ok((tran, faction, visibility)) = parent_query.get(attached_to.0) else {
+            continue;
+        };
+        if fogged(visibility) {
+            continue;
+        }
+        let base = tran.translation.truncate();
+        let color = faction_color(*faction).with_alpha(0.4);
+
+        let edge = |rot: AbsRot| base + rot.to_quat().mul_vec3(Vec3::Y * range.0 as f32).truncate();
+        let center = edge(heading.0);
+        let cw = edge(heading.0.cw_edge(arc.current));
+        let ccw = edge(heading.0.ccw_edge(arc.current));
+
+        gizmos.line_2d(base, cw, color);
+        gizmos.line_2d(base, ccw, color);
+        gizmos.short_arc_2d_between(base, center, cw, color);
+        gizmos.short_arc_2d_between(base, center, ccw, color);
+    }
+}
+
+// The main camera view
+fn render_minimap_view(
+    mut gizmos: Gizmos<MinimapGizmos>,
+    camera: Single<(&Transform, &Projection), With<CameraRig>>,
+) {
+    let (tran, proj) = camera.into_inner();
+    if let Projection::Orthographic(ortho) = proj {
+        gizmos.rect_2d(
+            Isometry2d::from_translation(tran.translation.truncate() + ortho.area.center()),
+            ortho.area.size(),
+            bevy::color::palettes::css::WHITE,
+        );
+    }
+}
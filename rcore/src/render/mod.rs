@@ -9,18 +9,37 @@ use bevy_prototype_lyon::prelude::ShapePlugin;
 
 mod arena;
 pub mod camera;
+pub mod debug_overlay;
+mod effect;
+mod fog;
 mod gizmo;
+pub mod input;
+pub mod inspector;
+mod minimap;
+mod palette;
 mod shape;
+mod trail;
 
 use arena::arena_bounds_setup;
+use input::InputAction;
+use input::InputMap;
+use palette::hull_color;
+use palette::radar_color;
 use shape::get_radar;
+use shape::get_resource_field;
 use shape::get_ship;
 
+use crate::attach::AttachedTo;
+use crate::destruction::Wreck;
 use crate::time::TimeMsg;
+use crate::mining::ResourceField;
 use crate::radar::Radar;
+use crate::ship::Faction;
 use crate::ship::Ship;
-use crate::weapon::RenderDebugWarhead;
-use crate::weapon::RenderDebugWeapon;
+use crate::snapshot::SnapshotMessage;
+use crate::style::HULL_STROKE;
+use crate::style::WRECK_STROKE;
+use crate::weapon::Health;
 
 // Render plugin to make it easy to keep render segmented off
 pub struct RenderPlugin;
@@ -28,6 +47,20 @@ impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
         // Graphics (lyon)
         app.add_plugins(ShapePlugin)
+            // Key bindings
+            .add_plugins(input::InputPlugin)
+            // Beams, blasts and exhaust
+            .add_plugins(effect::EffectPlugin)
+            // Ship trails and predicted paths
+            .add_plugins(trail::TrailPlugin)
+            // Faction view
+            .add_plugins(fog::FogPlugin)
+            // Runtime debug gizmo toggles
+            .add_plugins(debug_overlay::DebugOverlayPlugin)
+            // Ship selection and inspector panel
+            .add_plugins(inspector::InspectorPlugin)
+            // Arena overview in the corner
+            .add_plugins(minimap::MinimapPlugin)
             // FPS
             .add_plugins(FpsOverlayPlugin {
                 config: FpsOverlayConfig {
@@ -62,7 +95,14 @@ impl Plugin for RenderPlugin {
             // Handle assigning a lyon shape to entities
             .add_systems(
                 PostUpdate,
-                (apply_ship_shape, apply_radar_shape).before(BuildShapes),
+                (
+                    apply_ship_shape,
+                    apply_wreck_shape,
+                    apply_radar_shape,
+                    apply_resource_field_shape,
+                    apply_health_tint,
+                )
+                    .before(BuildShapes),
             )
             // Gizmos
             .add_systems(
@@ -76,45 +116,68 @@ impl Plugin for RenderPlugin {
                     gizmo::shield_health,
                     arena::arena_grid,
                     render_time_control,
+                    toggle_gizmos,
+                    request_snapshot,
                 ),
             )
             // Time controls
             .add_systems(
                 PreUpdate,
                 manage_time_control,
-            )
-            // Temporary weapon render via gizmos
-            .add_systems(
-                RunFixedMainLoop,
-                (
-                    render_debug_weapon.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
-                    render_debug_warhead.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
-                ),
             );
     }
 }
 
+// Pointer is over a UI node (panel or button), clicks belong to the UI and not the world
+pub(super) fn pointer_over_ui(interactions: &Query<&Interaction>) -> bool {
+    interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
 fn manage_time_control(
     key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
     time: Res<Time<Virtual>>,
     mut message: MessageWriter<TimeMsg>,
 ) {
     let exp = time.relative_speed().log2().round() as i8;
 
-    if key_input.just_pressed(KeyCode::Space) {
+    if input_map.just_pressed(InputAction::Pause, &key_input) {
         message.write(TimeMsg::Pause(!time.is_paused()));
     }
-    if key_input.just_pressed(KeyCode::BracketLeft) {
+    if input_map.just_pressed(InputAction::SlowDown, &key_input) {
         message.write(TimeMsg::Speed(exp - 1));
     }
-    if key_input.just_pressed(KeyCode::BracketRight) {
+    if input_map.just_pressed(InputAction::SpeedUp, &key_input) {
         message.write(TimeMsg::Speed(exp + 1));
     }
-    if key_input.just_pressed(KeyCode::Period) {
+    if input_map.just_pressed(InputAction::Step, &key_input) {
         message.write(TimeMsg::Step(1));
     }
 }
 
+fn request_snapshot(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut message: MessageWriter<SnapshotMessage>,
+) {
+    if input_map.just_pressed(InputAction::Snapshot, &key_input) {
+        message.write(SnapshotMessage);
+    }
+}
+
+fn toggle_gizmos(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut config_store: ResMut<GizmoConfigStore>,
+) {
+    if input_map.just_pressed(InputAction::ToggleGizmos, &key_input) {
+        let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+        config.enabled = !config.enabled;
+    }
+}
+
 #[derive(Component)]
 struct TimeControlMarker;
 
@@ -163,70 +226,62 @@ fn render_time_control(
     text.0 = format!("{exp} - {pause}");
 }
 
-fn apply_ship_shape(query: Query<(Entity, &Ship), Without<Shape>>, mut commands: Commands) {
-    for (entity, ship) in query.iter() {
+fn apply_ship_shape(
+    query: Query<(Entity, &Ship, &Faction, Option<&Health>), Without<Shape>>,
+    mut commands: Commands,
+) {
+    for (entity, ship, faction, health) in query.iter() {
         commands.entity(entity).insert(get_ship(
             ship.0,
-            bevy::color::palettes::css::GREEN,
-            bevy::color::palettes::css::BLACK,
+            Some(hull_color(*faction, health)),
+            HULL_STROKE,
         ));
     }
 }
 
-fn apply_radar_shape(query: Query<(Entity, &Radar), Without<Shape>>, mut commands: Commands) {
-    for (entity, _) in query.iter() {
+// Hull color follows the damage taken
+#[expect(clippy::type_complexity)]
+fn apply_health_tint(
+    mut query: Query<(&Faction, &Health, &mut Shape), (With<Ship>, Changed<Health>)>,
+) {
+    for (faction, health, mut shape) in query.iter_mut() {
+        if let Some(fill) = shape.fill.as_mut() {
+            fill.color = hull_color(*faction, Some(health)).into();
+        }
+    }
+}
+
+// Wrecks are only the outline of the hull
+fn apply_wreck_shape(query: Query<(Entity, &Wreck), Without<Shape>>, mut commands: Commands) {
+    for (entity, wreck) in query.iter() {
         commands
             .entity(entity)
-            .insert(get_radar(bevy::color::palettes::css::MAROON));
+            .insert(get_ship(wreck.0, None, WRECK_STROKE));
     }
 }
 
-#[expect(clippy::needless_pass_by_value)]
-fn render_debug_weapon(
-    mut gizmos: Gizmos,
+#[expect(clippy::type_complexity)]
+fn apply_radar_shape(
+    query: Query<(Entity, &AttachedTo), (With<Radar>, Without<Shape>)>,
+    parent_query: Query<&Faction>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut RenderDebugWeapon)>,
-    time: Res<Time>,
 ) {
-    for (entity, mut render) in query.iter_mut() {
-        render.fade.tick(time.delta());
-
-        // TODO: render the beam thicker
-        gizmos.line_2d(
-            render.origin,
-            render.target,
-            bevy::color::palettes::css::RED,
-        );
-
-        // Check if fade has expired?
-        // if so, despawn
-        if render.fade.is_finished() {
-            commands.entity(entity).despawn();
-        }
+    for (entity, attached_to) in query.iter() {
+        let faction = parent_query.get(attached_to.0).copied().unwrap_or_default();
+        commands
+            .entity(entity)
+            .insert(get_radar(radar_color(faction)));
     }
 }
 
-#[expect(clippy::needless_pass_by_value)]
-fn render_debug_warhead(
-    mut gizmos: Gizmos,
+fn apply_resource_field_shape(
+    query: Query<Entity, (With<ResourceField>, Without<Shape>)>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut RenderDebugWarhead)>,
-    time: Res<Time>,
 ) {
-    for (entity, mut render) in query.iter_mut() {
-        render.fade.tick(time.delta());
-
-        // TODO: render the beam thicker
-        gizmos.circle_2d(
-            Isometry2d::from_translation(render.origin),
-            crate::weapon::DISTANCE as f32,
-            bevy::color::palettes::css::RED,
-        );
-
-        // Check if fade has expired?
-        // if so, despawn
-        if render.fade.is_finished() {
-            commands.entity(entity).despawn();
-        }
+    for entity in query.iter() {
+        commands.entity(entity).insert(get_resource_field(
+            bevy::color::palettes::css::DARK_GRAY,
+            bevy::color::palettes::css::SILVER,
+        ));
     }
 }
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::ship::Faction;
+use crate::style::faction_color;
+use crate::weapon::Health;
+
+// Render colors:
+// - The hull, radar and minimap are all painted from the faction team color (`crate::style`).
+// - Hulls darken toward a burnt red as they lose health, wrecks are gray outlines.
+
+// What a hull at 0 HP would look like
+const DAMAGE_TINT: Srgba = Srgba::rgb(0.25, 0.05, 0.02);
+// How far toward the damage tint a dying hull goes, keeps the team readable
+const DAMAGE_MIX: f32 = 0.75;
+
+// Team color tinted by the damage taken
+pub(super) fn hull_color(faction: Faction, health: Option<&Health>) -> Srgba {
+    let damage = health.map_or(0.0, |health| {
+        1.0 - f32::from(health.current) / f32::from(health.maximum.max(1))
+    });
+    faction_color(faction).mix(&DAMAGE_TINT, damage.clamp(0.0, 1.0) * DAMAGE_MIX)
+}
+
+// Radar dish, has to stand out on top of the hull
+pub(super) fn radar_color(faction: Faction) -> Srgba {
+    faction_color(faction).darker(0.25)
+}
+
+#[test]
+fn test_hull_color() {
+    use crate::style::FACTION_PALETTE;
+
+    let faction = Faction(5);
+    assert_eq!(hull_color(faction, None), FACTION_PALETTE[1]);
+
+    let health = |current| Health {
+        current,
+        maximum: 100,
+    };
+    assert_eq!(hull_color(faction, Some(&health(100))), FACTION_PALETTE[1]);
+    assert_eq!(
+        hull_color(faction, Some(&health(0))),
+        FACTION_PALETTE[1].mix(&DAMAGE_TINT, DAMAGE_MIX)
+    );
+}
@@ -9,24 +9,33 @@ use bevy::prelude::Srgba;
 use bevy::prelude::Vec2;
 
 use crate::ship::ShipClass;
+use crate::style::hull_outline;
 
-pub(super) fn get_ship(class: ShipClass, fill: Srgba, stroke: Srgba) -> Shape {
-    let ship_path = match class {
-        ShipClass::Large => todo!(),
-        ShipClass::Medium => ShapePath::new()
-            .move_to(Vec2::new(0.0, 200.0))
-            .line_to(Vec2::new(100.0, -200.0))
-            .line_to(Vec2::new(0.0, -100.0))
-            .line_to(Vec2::new(-100.0, -200.0))
-            .close(),
-        ShipClass::Small => todo!(),
-        ShipClass::Tiny => todo!(),
+// Hull outline only without a fill (wrecks)
+pub(super) fn get_ship(class: ShipClass, fill: Option<Srgba>, stroke: Srgba) -> Shape {
+    let outline = hull_outline(class);
+    let mut ship_path = ShapePath::new().move_to(outline[0]);
+    for &point in &outline[1..] {
+        ship_path = ship_path.line_to(point);
+    }
+    let ship_path = ship_path.close();
+
+    // Keep the outline in proportion to the hull
+    let stroke_width = match class {
+        ShipClass::Large => 30.0,
+        ShipClass::Medium => 20.0,
+        ShipClass::Small => 12.0,
+        ShipClass::Tiny => 6.0,
     };
 
-    ShapeBuilder::with(&ship_path)
-        .fill(Fill::color(fill))
-        .stroke(Stroke::new(stroke, 20.0))
-        .build()
+    let stroke = Stroke::new(stroke, stroke_width);
+    match fill {
+        Some(fill) => ShapeBuilder::with(&ship_path)
+            .fill(Fill::color(fill))
+            .stroke(stroke)
+            .build(),
+        None => ShapeBuilder::with(&ship_path).stroke(stroke).build(),
+    }
 }
 
 pub(super) fn get_radar(stroke: Srgba) -> Shape {
@@ -45,3 +54,20 @@ pub(super) fn get_radar(stroke: Srgba) -> Shape {
         .stroke(Stroke::new(stroke, 15.0))
         .build()
 }
+
+pub(super) fn get_resource_field(fill: Srgba, stroke: Srgba) -> Shape {
+    let field_path = ShapePath::new()
+        .move_to(Vec2::new(0.0, 200.0))
+        .line_to(Vec2::new(150.0, 120.0))
+        .line_to(Vec2::new(190.0, -60.0))
+        .line_to(Vec2::new(60.0, -190.0))
+        .line_to(Vec2::new(-120.0, -160.0))
+        .line_to(Vec2::new(-200.0, 20.0))
+        .line_to(Vec2::new(-110.0, 170.0))
+        .close();
+
+    ShapeBuilder::with(&field_path)
+        .fill(Fill::color(fill))
+        .stroke(Stroke::new(stroke, 15.0))
+        .build()
+}
@@ -0,0 +1,185 @@
+use avian2d::prelude::LinearVelocity;
+use avian2d::prelude::Position;
+use bevy::prelude::*;
+
+use std::collections::VecDeque;
+
+use crate::movement::Rcs;
+use crate::movement::Thrust;
+use crate::movement::predict_path;
+use crate::rotation::Heading;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::style::faction_color;
+use crate::time::Ticks;
+
+use super::fog::fogged;
+use super::input::InputAction;
+use super::input::InputMap;
+
+// Motion trails:
+// - Every ship keeps a trail of where it's been, sampled every few sim ticks, drawn as a line that
+//   fades out toward the oldest sample. Sampled on the sim ticks so it stops while paused.
+// - Optional predicted path, the next couple of seconds if the ship holds its current heading and
+//   acceleration, run through the same thrust model as `apply_thrust`. Ignores the power and
+//   propellant, so a ship about to run dry will fall short of it.
+// - Own gizmo group so the debug gizmo toggle doesn't hide them.
+pub(super) struct TrailPlugin;
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TrailSettings>()
+            .init_gizmo_group::<TrailGizmos>()
+            .add_systems(
+                Update,
+                (
+                    trail_hotkeys,
+                    apply_trail,
+                    sample_trail,
+                    render_trail,
+                    render_prediction,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Ticks between the trail samples
+const TRAIL_INTERVAL: u64 = 8;
+// Samples kept, 8 seconds at 8 ticks apart
+const TRAIL_SAMPLES: usize = 64;
+const TRAIL_ALPHA: f32 = 0.6;
+// Ticks to predict ahead, 4 seconds
+const PREDICT_TICKS: u32 = 256;
+// Only draw every few predicted positions
+const PREDICT_STRIDE: usize = 4;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct TrailGizmos;
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub(super) struct TrailSettings {
+    pub trails: bool,
+    pub prediction: bool,
+}
+
+impl Default for TrailSettings {
+    fn default() -> Self {
+        Self {
+            trails: true,
+            prediction: false,
+        }
+    }
+}
+
+// Past positions, oldest first
+#[derive(Component, Debug, Default)]
+struct Trail {
+    samples: VecDeque<Vec2>,
+    // Tick of the last sample
+    last: u64,
+}
+
+fn trail_hotkeys(
+    key_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut settings: ResMut<TrailSettings>,
+) {
+    if input_map.just_pressed(InputAction::ToggleTrails, &key_input) {
+        settings.trails = !settings.trails;
+    }
+    if input_map.just_pressed(InputAction::TogglePrediction, &key_input) {
+        settings.prediction = !settings.prediction;
+    }
+}
+
+fn apply_trail(mut commands: Commands, query: Query<Entity, Added<Ship>>) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(Trail::default());
+    }
+}
+
+// Keeps sampling while the trails are hidden, so they are all there when turned back on
+fn sample_trail(ticks: Res<Ticks>, mut query: Query<(&mut Trail, &Position)>) {
+    for (mut trail, pos) in query.iter_mut() {
+        if !trail.samples.is_empty() && !ticks.is_ready(trail.last, TRAIL_INTERVAL) {
+            continue;
+        }
+        if trail.samples.len() == TRAIL_SAMPLES {
+            trail.samples.pop_front();
+        }
+        trail.samples.push_back(pos.0);
+        trail.last = ticks.now();
+    }
+}
+
+fn render_trail(
+    mut gizmos: Gizmos<TrailGizmos>,
+    settings: Res<TrailSettings>,
+    query: Query<(&Trail, &Faction, &Transform, Option<&Visibility>)>,
+) {
+    if !settings.trails {
+        return;
+    }
+
+    for (trail, faction, tran, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
+        let color = faction_color(*faction);
+        let count = trail.samples.len() as f32;
+
+        // Fade in from the oldest sample, ends at the ship itself
+        let points = trail
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(idx, pos)| (*pos, color.with_alpha(TRAIL_ALPHA * idx as f32 / count)))
+            .chain(std::iter::once((
+                tran.translation.truncate(),
+                color.with_alpha(TRAIL_ALPHA),
+            )));
+        gizmos.linestrip_gradient_2d(points);
+    }
+}
+
+#[expect(clippy::type_complexity)]
+fn render_prediction(
+    mut gizmos: Gizmos<TrailGizmos>,
+    settings: Res<TrailSettings>,
+    query: Query<(
+        &Position,
+        &LinearVelocity,
+        &Heading,
+        &Thrust,
+        Option<&Rcs>,
+        &Faction,
+        Option<&Visibility>,
+    )>,
+) {
+    if !settings.prediction {
+        return;
+    }
+
+    for (pos, velocity, heading, thrust, rcs, faction, visibility) in query.iter() {
+        if fogged(visibility) {
+            continue;
+        }
+        let mut acceleration = thrust.world_acceleration(heading.0);
+        if let Some(rcs) = rcs {
+            acceleration += rcs.acceleration(heading.0);
+        }
+        let path = predict_path(
+            pos.0,
+            velocity.0,
+            acceleration,
+            thrust.velocity_limit,
+            PREDICT_TICKS,
+        );
+
+        let color = faction_color(*faction).with_alpha(0.4);
+        gizmos.linestrip_2d(
+            std::iter::once(pos.0).chain(path.into_iter().step_by(PREDICT_STRIDE)),
+            color,
+        );
+    }
+}
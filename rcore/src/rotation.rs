@@ -1,4 +1,5 @@
 use avian2d::interpolation::RotationInterpolation;
+use avian2d::prelude::RigidBody;
 use avian2d::prelude::Rotation;
 use bevy::prelude::*;
 
@@ -102,18 +103,21 @@ pub(crate) fn apply_rotation(
         heading.0 += angle;
 
         // Mirror it into avian for physics/etc..
-        // Arc modules don't have rigid body so they don't have Rotation
+        // Radar doesn't have Rotation, the shield sensor does for its collider
         if let Some(mut rotation) = opt_rotation {
             *rotation = heading.0.to_rotation();
         }
     }
 }
 
-// Arc modules (radar/shield) don't have Avian Rotation, Update render transform
-// directly for these. (The others are handled by Avian).
+// Arc modules (radar/shield) aren't rigid bodies so Avian doesn't write their transform, update it
+// directly for these. (The ships are handled by Avian).
+//
+// NOTE: The shield sensor has a Rotation, Avian syncs the transform back into it before the
+// physics step so the transform has to follow the heading or the collider snaps back.
 #[expect(clippy::type_complexity)]
 pub(crate) fn propagate_heading_transform(
-    mut query: Query<(&Heading, &mut Transform), (Changed<Heading>, Without<Rotation>)>,
+    mut query: Query<(&Heading, &mut Transform), (Changed<Heading>, Without<RigidBody>)>,
 ) {
     for (heading, mut transform) in query.iter_mut() {
         transform.rotation = heading.0.to_quat();
@@ -6,6 +6,22 @@ use dyn_clone::DynClone;
 use std::fmt;
 
 use crate::attach::Attachments;
+use crate::autopilot::Autopilot;
+use crate::autopilot::AutopilotMessage;
+use crate::autopilot::AutopilotOrder;
+use crate::destruction::Destroyed;
+use crate::destruction::Wreck;
+use crate::ecm::BearingMessage;
+use crate::ecm::DecoyLauncher;
+use crate::ecm::Emission;
+use crate::ecm::Jammer;
+use crate::fabrication::BuildCompleteMessage;
+use crate::fabrication::BuildOrder;
+use crate::fabrication::FabricationBay;
+use crate::impact::ImpactMessage;
+use crate::impact::hull_class;
+use crate::movement::Propellant;
+use crate::movement::Rcs;
 use crate::movement::Thrust;
 use crate::rotation::Heading;
 use crate::rotation::TargetHeading;
@@ -20,6 +36,15 @@ use crate::weapon::FireDebugWeaponMessage;
 use crate::FixedGameSystem;
 use crate::math::AbsRot;
 use crate::math::RelRot;
+use crate::mining::Cargo;
+use crate::mining::FactionResources;
+use crate::mining::TransferMessage;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::sleep::Sleep;
+use crate::sleep::WakeMessage;
+use crate::time::Ticks;
 
 // TODO: Design
 //
@@ -63,15 +88,48 @@ pub struct ShipStatus {
     pub velocity: IVec2,
     pub acceleration: i32,
     pub heading: AbsRot,
+    // None for an unlimited tank
+    pub propellant: Option<u32>,
+    pub cargo: u32,
+    pub cargo_capacity: u32,
+    // Resources held across the whole faction
+    pub faction_cargo: u32,
+    // Order the autopilot is flying, None if the script has the controls
+    pub autopilot: Option<AutopilotOrder>,
+}
+
+// What the ship ran into and how hard
+pub struct ShipCollision {
+    pub target_entity: Entity,
+    pub target_class: ShipClass,
+    pub relative_speed: u32,
+    // Hull damage taken from the impact, before the shield got a go at it
+    pub damage: u16,
 }
 
 // Initial attempt of building a ship action structure for what to do
 #[must_use]
+#[derive(Debug, Clone)]
 pub struct ShipAction {
     pub heading: RelRot,
     pub acceleration: i32,
+    // Ship-local RCS thrust (+Y forward, +X starboard)
+    pub rcs: IVec2,
     pub radar_heading: RelRot,
     pub target_entity: Option<Entity>,
+    // Queue up a ship on the fabrication bay
+    pub fabricate: Option<BuildOrder>,
+    // Hand cargo over to a friendly ship
+    pub transfer: Option<(Entity, u32)>,
+    // Put the ship to sleep, shuts down the engine
+    pub sleep: Option<Sleep>,
+    // Switch the jammer on or off, None leaves it as is
+    pub jammer: Option<bool>,
+    // Launch a decoy at this velocity relative to the ship
+    pub decoy: Option<IVec2>,
+    // New autopilot order, Some(None) hands the controls back to the script. While the autopilot
+    // is flying the heading, acceleration and RCS are ignored.
+    pub autopilot: Option<Option<AutopilotOrder>>,
 }
 
 impl Default for ShipAction {
@@ -85,8 +143,15 @@ impl ShipAction {
         Self {
             heading: RelRot(0),
             acceleration: 0,
+            rcs: IVec2::ZERO,
             radar_heading: RelRot(0),
             target_entity: None,
+            fabricate: None,
+            transfer: None,
+            sleep: None,
+            jammer: None,
+            decoy: None,
+            autopilot: None,
         }
     }
 
@@ -100,6 +165,11 @@ impl ShipAction {
         self
     }
 
+    pub fn rcs(mut self, x: i32, y: i32) -> Self {
+        self.rcs = IVec2::new(x, y);
+        self
+    }
+
     pub fn radar_heading(mut self, hdr: RelRot) -> Self {
         self.radar_heading = hdr;
         self
@@ -109,6 +179,41 @@ impl ShipAction {
         self.target_entity = target;
         self
     }
+
+    pub fn fabricate(mut self, order: BuildOrder) -> Self {
+        self.fabricate = Some(order);
+        self
+    }
+
+    pub fn transfer(mut self, target: Entity, amount: u32) -> Self {
+        self.transfer = Some((target, amount));
+        self
+    }
+
+    pub fn sleep(mut self, sleep: Sleep) -> Self {
+        self.sleep = Some(sleep);
+        self
+    }
+
+    pub fn jammer(mut self, active: bool) -> Self {
+        self.jammer = Some(active);
+        self
+    }
+
+    pub fn decoy(mut self, x: i32, y: i32) -> Self {
+        self.decoy = Some(IVec2::new(x, y));
+        self
+    }
+
+    pub fn autopilot(mut self, order: AutopilotOrder) -> Self {
+        self.autopilot = Some(Some(order));
+        self
+    }
+
+    pub fn disengage(mut self) -> Self {
+        self.autopilot = Some(None);
+        self
+    }
 }
 
 pub trait ShipScript: DynClone + Send + Sync + 'static {
@@ -116,7 +221,22 @@ pub trait ShipScript: DynClone + Send + Sync + 'static {
 
     // TODO: add ship status to these as well
     fn on_contact(&mut self, target_pos: IVec2, target_entity: Entity);
-    fn on_collision(&mut self);
+    fn on_collision(&mut self, collision: &ShipCollision);
+
+    // Fabrication bay launched a ship
+    fn on_build_complete(&mut self, _class: ShipClass) {}
+
+    // Ship woke up from sleep
+    fn on_wake(&mut self) {}
+
+    // Autopilot reached its order, it keeps flying it till told otherwise
+    fn on_autopilot(&mut self, _order: AutopilotOrder) {}
+
+    // Passive sensor picked up an enemy emitter, bearing only
+    fn on_bearing(&mut self, _bearing: AbsRot, _emission: Emission) {}
+
+    // Last call before the ship gets replaced by a wreck
+    fn on_destroyed(&mut self) {}
 }
 dyn_clone::clone_trait_object!(ShipScript);
 
@@ -131,6 +251,10 @@ impl fmt::Debug for Script {
     }
 }
 
+// Last action the script returned, for inspecting the ship, updated in place every tick
+#[derive(Component, Debug, Clone)]
+pub struct LastAction(pub ShipAction);
+
 #[derive(Resource)]
 struct ScriptTimer(Timer);
 
@@ -146,7 +270,11 @@ impl Plugin for ScriptPlugins {
                 // The problem is right now collision/contact is every frame due to the
                 // Message queue being frame based and dropped after a frame.
                 (
-                    process_on_collision.before(process_on_contact),
+                    process_on_wake.before(process_on_autopilot),
+                    process_on_autopilot.before(process_on_build_complete),
+                    process_on_build_complete.before(process_on_collision),
+                    process_on_collision.before(process_on_bearing),
+                    process_on_bearing.before(process_on_contact),
                     process_on_contact.before(process_on_update),
                     process_on_update,
                 )
@@ -155,42 +283,84 @@ impl Plugin for ScriptPlugins {
     }
 }
 
+fn process_on_wake(mut wake_messages: MessageReader<WakeMessage>, mut query: Query<&mut Script>) {
+    for WakeMessage(ship) in wake_messages.read() {
+        if let Ok(mut ship_script) = query.get_mut(*ship) {
+            ship_script.script.on_wake();
+        }
+    }
+}
+
+fn process_on_autopilot(
+    mut autopilot_messages: MessageReader<AutopilotMessage>,
+    mut query: Query<&mut Script, Without<Sleep>>,
+) {
+    for AutopilotMessage(ship, order) in autopilot_messages.read() {
+        if let Ok(mut ship_script) = query.get_mut(*ship) {
+            ship_script.script.on_autopilot(*order);
+        }
+    }
+}
+
+// The computer is off while the ship sleeps, anything that happens in the meantime is missed
+fn process_on_build_complete(
+    mut complete_messages: MessageReader<BuildCompleteMessage>,
+    mut query: Query<&mut Script, Without<Sleep>>,
+) {
+    for BuildCompleteMessage(ship, class) in complete_messages.read() {
+        if let Ok(mut ship_script) = query.get_mut(*ship) {
+            ship_script.script.on_build_complete(*class);
+        }
+    }
+}
+
 fn process_on_collision(
-    mut collision_events: MessageReader<CollisionStart>,
-    mut query: Query<(Entity, &mut Script)>,
+    mut impact_messages: MessageReader<ImpactMessage>,
+    mut query: Query<&mut Script, Without<Sleep>>,
+    class_query: Query<AnyOf<(&Ship, &Wreck)>>,
 ) {
     // Handle collision events first
-    for event in collision_events.read() {
-        if let Ok([(_, e1_script), (_, e2_script)]) =
-            query.get_many_mut([event.collider1, event.collider2])
-        {
-            for mut ship_script in [e1_script, e2_script] {
-                // Invoke collision handler
-                ship_script.script.on_collision();
-            }
-        } else {
-            println!(
-                "ERROR - SCRIPT - CollisionStart({:?}, {:?})",
-                event.collider1, event.collider2
-            );
+    for ImpactMessage(ship, target, relative_speed, damage) in impact_messages.read() {
+        // Either ship may have been destroyed by the impact
+        let (Ok(mut ship_script), Ok(target_hull)) =
+            (query.get_mut(*ship), class_query.get(*target))
+        else {
+            continue;
+        };
+
+        // Invoke collision handler
+        ship_script.script.on_collision(&ShipCollision {
+            target_entity: *target,
+            target_class: hull_class(target_hull),
+            relative_speed: *relative_speed,
+            damage: *damage,
+        });
+    }
+}
+
+fn process_on_bearing(
+    mut bearing_messages: MessageReader<BearingMessage>,
+    mut query: Query<&mut Script, Without<Sleep>>,
+) {
+    for BearingMessage(ship, bearing, emission) in bearing_messages.read() {
+        if let Ok(mut ship_script) = query.get_mut(*ship) {
+            ship_script.script.on_bearing(*bearing, *emission);
         }
     }
 }
 
 fn process_on_contact(
     mut contact_messages: MessageReader<ContactMessage>,
-    mut query: Query<(Entity, &Position, &mut Script)>,
+    mut query: Query<&mut Script>,
+    position_query: Query<&Position>,
 ) {
     // Invoke the script for contact
     for contact_message in contact_messages.read() {
         let ContactMessage(e1, e2) = contact_message;
-        // TODO: right now with the ContactEvent being copies it leads to aliased query here,
-        // This should be fixed once we have proper contact event that does not refer to self
-        if let Ok([(_, _, mut e1_script), (e2_entity, e2_pos, _)]) =
-            query.get_many_mut([*e1, *e2])
-        {
+        // Target may not be a ship (decoys)
+        if let (Ok(mut e1_script), Ok(e2_pos)) = (query.get_mut(*e1), position_query.get(*e2)) {
             // E1 knows where e2 is
-            e1_script.script.on_contact(e2_pos.0.as_ivec2(), e2_entity);
+            e1_script.script.on_contact(e2_pos.0.as_ivec2(), *e2);
         } else {
             println!("ERROR - SCRIPT - {contact_message:?}");
         }
@@ -218,13 +388,19 @@ fn process_on_contact(
     clippy::type_complexity
 )]
 fn process_on_update(
+    mut commands: Commands,
     time: Res<Time>,
+    ticks: Res<Ticks>,
     mut timer: ResMut<ScriptTimer>,
-    mut query: Query<(Entity, &mut Script)>,
+    // Destroyed ships are gone by the end of the tick, no more orders
+    mut query: Query<
+        (Entity, &mut Script, Option<&mut LastAction>),
+        (Without<Sleep>, Without<Destroyed>),
+    >,
     mut ship_query: Query<
         (
             &LinearVelocity,
-            &mut Thrust,
+            Option<&mut Thrust>,
             &Position,
             &mut TargetHeading,
             &Heading,
@@ -233,31 +409,84 @@ fn process_on_update(
         Without<Radar>,
     >,
     target_query: Query<Entity>,
+    mut bay_query: Query<(&mut FabricationBay, &Ship)>,
+    mut hardware_query: Query<(
+        Option<&mut Rcs>,
+        Option<&mut Jammer>,
+        Option<&mut DecoyLauncher>,
+        Option<&mut Autopilot>,
+    )>,
+    cargo_query: Query<(&Cargo, &Faction, Option<&Propellant>)>,
+    resources: Res<FactionResources>,
     mut radar_query: Query<&mut TargetHeading, With<Radar>>,
     mut l_message: MessageWriter<FireDebugWeaponMessage>,
     mut w_message: MessageWriter<FireDebugWarheadMessage>,
     mut m_message: MessageWriter<FireDebugMissileMessage>,
+    mut t_message: MessageWriter<TransferMessage>,
 ) {
     // handle normal on_update ticks
     if timer.0.tick(time.delta()).just_finished() {
-        for (entity, mut ship_script) in query.iter_mut() {
+        for (entity, mut ship_script, last_action) in query.iter_mut() {
             let ship = ship_query.get(entity).expect("ship");
+            let (cargo, faction, propellant) = cargo_query.get(entity).expect("cargo");
+            let autopilot = hardware_query
+                .get(entity)
+                .ok()
+                .and_then(|hardware| hardware.3.and_then(|autopilot| autopilot.order));
 
             let ship_status = ShipStatus {
                 position: ship.2.0.as_ivec2(),
                 velocity: ship.0.0.as_ivec2(),
-                acceleration: ship.1.acceleration,
+                // Engine may have been shot off
+                acceleration: ship.1.map_or(0, |thrust| thrust.acceleration),
                 heading: ship.4.0,
+                propellant: propellant.map(|tank| tank.current),
+                cargo: cargo.current,
+                cargo_capacity: cargo.capacity,
+                faction_cargo: resources.held(*faction),
+                autopilot,
             };
 
-            let res = ship_script.script.on_update(&ship_status);
+            let mut res = ship_script.script.on_update(&ship_status);
+
+            // Engine shuts down for the sleep, the ship coasts along
+            if let Some(mut sleep) = res.sleep {
+                sleep.since = ticks.now();
+                commands.entity(entity).insert(sleep);
+                res.acceleration = 0;
+                res.rcs = IVec2::ZERO;
+            }
 
-            // Always apply
-            let mut thrust = ship_query.get_mut(entity).expect("thrust").1;
-            thrust.acceleration = res.acceleration;
+            let mut piloted = false;
+            if let Ok((rcs, jammer, launcher, autopilot)) = hardware_query.get_mut(entity) {
+                // Sleep shuts the autopilot off along with the engine
+                if let Some(mut autopilot) = autopilot {
+                    if let Some(order) = res.autopilot {
+                        autopilot.engage(order);
+                    }
+                    piloted = autopilot.order.is_some() && res.sleep.is_none();
+                }
+                if let Some(mut rcs) = rcs
+                    && !piloted
+                {
+                    rcs.command(res.rcs);
+                }
+                if let (Some(mut jammer), Some(active)) = (jammer, res.jammer) {
+                    jammer.active = active;
+                }
+                if let (Some(mut launcher), Some(launch)) = (launcher, res.decoy) {
+                    launcher.launch = Some(launch);
+                }
+            }
 
-            let mut heading = ship_query.get_mut(entity).expect("heading").3;
-            heading.target += res.heading;
+            // Always apply, unless the autopilot has the controls
+            if !piloted {
+                let (_, thrust, _, mut heading, ..) = ship_query.get_mut(entity).expect("ship");
+                if let Some(mut thrust) = thrust {
+                    thrust.acceleration = res.acceleration;
+                }
+                heading.target += res.heading;
+            }
 
             // Radar is on an attachment to the ship
             if let Some(attachments) = ship_query.get(entity).expect("radar").5 {
@@ -268,6 +497,20 @@ fn process_on_update(
                 }
             }
 
+            // Orders for a ship without a bay, or that it can't build, are dropped
+            if let Some(order) = res.fabricate.take() {
+                let queued = bay_query
+                    .get_mut(entity)
+                    .is_ok_and(|(mut bay, builder)| bay.queue(builder.0, order));
+                if !queued {
+                    println!("Build order rejected - {entity:?}");
+                }
+            }
+
+            if let Some((target, amount)) = res.transfer {
+                t_message.write(TransferMessage(entity, target, amount));
+            }
+
             // For now emit a fire event
             if let Some(target) = res.target_entity
                 && let Ok(target_entity) = target_query.get(target)
@@ -280,6 +523,10 @@ fn process_on_update(
                 w_message.write(FireDebugWarheadMessage(entity));
                 m_message.write(FireDebugMissileMessage(entity));
             }
+
+            if let Some(mut last_action) = last_action {
+                last_action.0 = res;
+            }
         }
     }
 }
@@ -4,11 +4,28 @@ use avian2d::prelude::*;
 
 use crate::attach::AttachOffset;
 use crate::attach::AttachedTo;
+use crate::autopilot::Autopilot;
+use crate::destruction::Reactor;
+use crate::ecm::DecoyLauncher;
+use crate::ecm::Jammer;
+use crate::fabrication::FabricationBay;
+use crate::impact::MAX_FACTIONS;
+use crate::impact::shield_layers;
+use crate::impact::ship_layers;
 use crate::math::AbsRot;
+use crate::mining::Cargo;
+use crate::mining::Mining;
+use crate::module::Module;
+use crate::module::ModuleKind;
+use crate::power::Power;
+use crate::script::LastAction;
 use crate::script::Script;
+use crate::script::ShipAction;
 
 use crate::movement::MovDebug;
 use crate::movement::MovementBundle;
+use crate::movement::Propellant;
+use crate::movement::Rcs;
 
 use crate::rotation::RotDebug;
 use crate::rotation::RotationBundle;
@@ -24,6 +41,7 @@ use crate::weapon::Health;
 use crate::weapon::HealthDebug;
 
 use crate::weapon::ShieldBundle;
+use crate::weapon::ShieldCurve;
 use crate::weapon::ShieldHealthDebug;
 
 // INFO:
@@ -73,14 +91,15 @@ use crate::weapon::ShieldHealthDebug;
 // - faction
 // - heat - affect radar discovery & engine and other system health
 // - hp - collision/damaging (ammo/missiles/etc)
-// - shield -> Arc (direction + arc width) - less wide == more damage reduction where if its
-// pinsized its nearly 100% but if its 360 its nearly 0% damage reduction
-// - ship types (missiles, big, middle, small size ship)
 // - Ship energy (fuel for engine? and heat production)
-// - Ship construction (each ship can build a ship same size or smaller than itself?
 #[derive(Component)]
 pub struct Ship(pub ShipClass);
 
+// Side the ship fights for, missiles and shields ignores anything of the same faction
+// Up to `MAX_FACTIONS` factions
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Faction(pub u8);
+
 // There are several classes of ship:
 // 1. cruiser - large
 // 2. frigate - medium
@@ -94,7 +113,7 @@ pub struct Ship(pub ShipClass);
 //
 // This file would mostly serve a way to provide a render for the various
 // class of ship, and then we can feed it into the base mod to yield a 'ship'
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ShipClass {
     Large,
     Medium,
@@ -102,6 +121,108 @@ pub enum ShipClass {
     Tiny,
 }
 
+// Per class hull presets
+#[derive(Debug, Clone, Copy)]
+pub struct ClassStats {
+    pub collider_radius: f32,
+    pub shield_radius: i64,
+    pub health: u16,
+    // Share of the impact velocity a hull takes in a collision
+    pub mass: u32,
+    pub velocity_limit: u32,
+    pub rotation_limit: u16,
+    // RCS strafe and reverse acceleration, 0 for none
+    pub rcs_limit: i32,
+    // How many modules (engine, radar, weapon, shield) the hull can mount
+    pub module_slots: u8,
+    // Percentage of the radar range this hull can be detected at
+    pub radar_signature: u16,
+    pub cargo_capacity: u32,
+    // Fabrication cost, total power and resources drawn over the build ticks
+    pub build_ticks: u32,
+    pub build_power: u32,
+    pub build_resources: u32,
+}
+
+impl ShipClass {
+    // Ships can build ships of the same class or smaller than themselves
+    pub fn can_build(&self, class: Self) -> bool {
+        let size = |class: &Self| match class {
+            Self::Large => 3,
+            Self::Medium => 2,
+            Self::Small => 1,
+            Self::Tiny => 0,
+        };
+        size(self) >= size(&class)
+    }
+
+    // TODO: tune these, these are a rough first pass
+    pub fn stats(&self) -> ClassStats {
+        match self {
+            Self::Large => ClassStats {
+                collider_radius: 300.0,
+                shield_radius: 500,
+                health: 400,
+                mass: 1000,
+                velocity_limit: 60,
+                rotation_limit: 8,
+                rcs_limit: 2,
+                module_slots: 6,
+                radar_signature: 150,
+                cargo_capacity: 2000,
+                build_ticks: 1920,
+                build_resources: 800,
+                build_power: 3000,
+            },
+            Self::Medium => ClassStats {
+                collider_radius: 150.0,
+                shield_radius: 300,
+                health: 100,
+                mass: 200,
+                velocity_limit: 100,
+                rotation_limit: 16,
+                rcs_limit: 5,
+                module_slots: 5,
+                radar_signature: 100,
+                cargo_capacity: 500,
+                build_ticks: 1280,
+                build_resources: 300,
+                build_power: 1500,
+            },
+            Self::Small => ClassStats {
+                collider_radius: 75.0,
+                shield_radius: 150,
+                health: 50,
+                mass: 50,
+                velocity_limit: 150,
+                rotation_limit: 32,
+                rcs_limit: 10,
+                module_slots: 3,
+                radar_signature: 70,
+                cargo_capacity: 100,
+                build_ticks: 640,
+                build_resources: 100,
+                build_power: 600,
+            },
+            Self::Tiny => ClassStats {
+                collider_radius: 30.0,
+                shield_radius: 60,
+                health: 20,
+                mass: 10,
+                velocity_limit: 250,
+                rotation_limit: 64,
+                rcs_limit: 0,
+                module_slots: 2,
+                radar_signature: 40,
+                cargo_capacity: 0,
+                build_ticks: 128,
+                build_resources: 20,
+                build_power: 100,
+            },
+        }
+    }
+}
+
 // TODO: decouple the rendering stuff somewhat from the rest of the system. Ie we
 // still bundle the assets in the ECS, but have all of the system interact within
 // the ECS then after things settle -> have a system that takes the ship plugin content
@@ -120,12 +241,24 @@ pub enum ShipClass {
 // - Dig into ECS archtype to help with some of these setup stuff
 #[derive(Clone)]
 pub struct StarterShip {
+    class: ShipClass,
+    faction: Faction,
     movement: MovementBundle,
+    rcs: Rcs,
+    propellant: Option<Propellant>,
+    reactor: Option<Reactor>,
     rotation: RotationBundle,
     radar: RadarBundle,
     health: Health,
+    power: Power,
+    cargo: u32,
     shield: ShieldBundle,
-    warhead: Option<DebugWarhead>,
+    engine: Module,
+    weapon: Module,
+    fabricator: Module,
+    mining: Module,
+    jammer: Module,
+    loadout: Loadout,
     script: Script,
     debug: DebugShip,
 }
@@ -139,14 +272,29 @@ impl StarterShip {
 // Builder to make building a starter ship nicer
 #[must_use]
 pub struct ShipBuilder {
+    class: ShipClass,
+    faction: Faction,
     movement: MovementBundle,
+    propellant: Option<Propellant>,
+    reactor: Option<Reactor>,
     rotation: RotationBundle,
     radar: RadarBundle,
-    health: Health,
+    power: Power,
+    cargo: u32,
     shield: ShieldBundle,
-    warhead: Option<DebugWarhead>,
+    engine: Module,
+    weapon: Module,
+    fabricator: Module,
+    mining: Module,
+    jammer: Module,
+    loadout: Loadout,
     script: Script,
     debug: DebugShip,
+    // Overrides for the class presets, the rest gets filled in from the class at build
+    velocity_limit: Option<u32>,
+    rotation_limit: Option<u16>,
+    rcs: Option<Rcs>,
+    health: Option<u16>,
 }
 
 // Look into impl Bundler
@@ -156,22 +304,63 @@ impl ShipBuilder {
     pub fn new(script: Script) -> Self {
         // TODO: setup so that most of these components have default() or something so that
         // they can be more self-contained without having to build them up here in the builder
+        //
+        // The hull presets are placeholders here, they get filled in from the class at build
+        let stats = ShipClass::Medium.stats();
+
         Self {
-            movement: MovementBundle::new(IVec2::new(0, 0), IVec2::new(0, 0), 100, 0),
-            rotation: RotationBundle::new(AbsRot(0), AbsRot(0), 16),
+            class: ShipClass::Medium,
+            faction: Faction(0),
+            movement: MovementBundle::new(
+                IVec2::new(0, 0),
+                IVec2::new(0, 0),
+                stats.velocity_limit,
+                0,
+            ),
+            propellant: None,
+            reactor: None,
+            rotation: RotationBundle::new(AbsRot(0), AbsRot(0), stats.rotation_limit),
             radar: RadarBundle::new(AbsRot(0), AbsRot(0), 32, 32),
-            health: Health {
-                current: 100,
-                maximum: 100,
-            },
-            shield: ShieldBundle::new(AbsRot(0), AbsRot(0), 32, 32, 0.5, 100),
-            warhead: None,
+            shield: ShieldBundle::new(
+                AbsRot(0),
+                AbsRot(0),
+                32,
+                32,
+                ShieldCurve::default(),
+                stats.shield_radius,
+                100,
+            ),
+            power: Power::new(1000, 100),
+            cargo: 0,
+            engine: Module::new(ModuleKind::Engine),
+            weapon: Module::new(ModuleKind::Weapon),
+            fabricator: Module::new(ModuleKind::Fabricator),
+            mining: Module::new(ModuleKind::Mining),
+            jammer: Module::new(ModuleKind::Jammer),
+            loadout: Loadout::new(),
             script,
             debug: DebugShip::new(),
+            velocity_limit: None,
+            rotation_limit: None,
+            rcs: None,
+            health: None,
         }
     }
 
     // Settings
+    // Hull presets (health, velocity, rotation and rcs limits, shield radius) come from the class,
+    // anything set explicitly on the builder overrides them whatever the order
+    pub fn class(mut self, class: ShipClass) -> Self {
+        self.class = class;
+        self
+    }
+
+    // Capped by `MAX_FACTIONS`, past that there are no collision layers left for it
+    pub fn faction(mut self, faction: u8) -> Self {
+        self.faction = Faction(faction.min(MAX_FACTIONS - 1));
+        self
+    }
+
     pub fn position(mut self, x: i32, y: i32) -> Self {
         self.movement.position(x, y);
         self
@@ -188,7 +377,12 @@ impl ShipBuilder {
     }
 
     pub fn velocity_limit(mut self, limit: u32) -> Self {
-        self.movement.thrust.velocity_limit = limit;
+        self.velocity_limit = Some(limit);
+        self
+    }
+
+    pub fn rcs(mut self, lateral_limit: i32, axial_limit: i32, power_cost: u32) -> Self {
+        self.rcs = Some(Rcs::new(lateral_limit, axial_limit, power_cost));
         self
     }
 
@@ -200,13 +394,17 @@ impl ShipBuilder {
     }
 
     pub fn rotation_limit(mut self, limit: u16) -> Self {
-        self.rotation.target.limit = limit;
+        self.rotation_limit = Some(limit);
         self
     }
 
     pub fn health(mut self, health: u16) -> Self {
-        self.health.current = health;
-        self.health.maximum = health;
+        self.health = Some(health);
+        self
+    }
+
+    pub fn power(mut self, maximum: u32, generation: u32) -> Self {
+        self.power = Power::new(maximum, generation);
         self
     }
 
@@ -220,6 +418,11 @@ impl ShipBuilder {
         self
     }
 
+    pub fn radar_arc_limit(mut self, limit: u16) -> Self {
+        self.radar.arc_limit(limit);
+        self
+    }
+
     pub fn shield(mut self, rotation: AbsRot) -> Self {
         self.shield.rotation(rotation);
         self
@@ -235,13 +438,59 @@ impl ShipBuilder {
         self
     }
 
-    pub fn shield_damage_reduce(mut self, damage_reduce: f32) -> Self {
-        self.shield.damage_reduce(damage_reduce);
+    pub fn shield_arc_limit(mut self, limit: u16) -> Self {
+        self.shield.arc_limit(limit);
+        self
+    }
+
+    pub fn shield_curve(mut self, curve: ShieldCurve) -> Self {
+        self.shield.curve(curve);
+        self
+    }
+
+    pub fn shield_regen(mut self, rate: u32, power_cost: u32, recovery: u64) -> Self {
+        self.shield.regen(rate, power_cost, recovery);
+        self
+    }
+
+    pub fn module_health(mut self, kind: ModuleKind, health: u16) -> Self {
+        match kind {
+            ModuleKind::Radar => self.radar.module.health(health),
+            ModuleKind::Shield => self.shield.module.health(health),
+            ModuleKind::Weapon => self.weapon.health(health),
+            ModuleKind::Engine => self.engine.health(health),
+            ModuleKind::Fabricator => self.fabricator.health(health),
+            ModuleKind::Mining => self.mining.health(health),
+            ModuleKind::Jammer => self.jammer.health(health),
+        }
+        self
+    }
+
+    // Full tank, no tank is unlimited propellant
+    pub fn propellant(mut self, amount: u32) -> Self {
+        self.propellant = Some(Propellant::new(amount));
+        self
+    }
+
+    // Reactor goes up when the ship is destroyed, no reactor no explosion
+    pub fn reactor(mut self, damage: u16, radius: i64) -> Self {
+        self.reactor = Some(Reactor { damage, radius });
+        self
+    }
+
+    // Starting cargo, capped by the class cargo capacity
+    pub fn cargo(mut self, cargo: u32) -> Self {
+        self.cargo = cargo;
         self
     }
 
     pub fn warhead(mut self, damage: u16) -> Self {
-        self.warhead = Some(DebugWarhead { damage });
+        self.loadout.warhead = Some(damage);
+        self
+    }
+
+    pub fn loadout(mut self, loadout: Loadout) -> Self {
+        self.loadout = loadout;
         self
     }
 
@@ -256,20 +505,147 @@ impl ShipBuilder {
     }
 
     // TODO: can we do it as a ref so that we can make multiple ships quickly
-    pub fn build(self) -> StarterShip {
+    pub fn build(mut self) -> StarterShip {
+        let stats = self.class.stats();
+        self.movement.thrust.velocity_limit = self.velocity_limit.unwrap_or(stats.velocity_limit);
+        self.rotation.target.limit = self.rotation_limit.unwrap_or(stats.rotation_limit);
+        self.shield.radius(stats.shield_radius);
+        let health = self.health.unwrap_or(stats.health);
+
         StarterShip {
+            class: self.class,
+            faction: self.faction,
             movement: self.movement,
+            rcs: self
+                .rcs
+                .unwrap_or_else(|| Rcs::new(stats.rcs_limit, stats.rcs_limit, 10)),
+            propellant: self.propellant,
+            reactor: self.reactor,
             rotation: self.rotation,
             radar: self.radar,
-            health: self.health,
+            health: Health {
+                current: health,
+                maximum: health,
+            },
+            power: self.power,
+            cargo: self.cargo,
             shield: self.shield,
-            warhead: self.warhead,
+            engine: self.engine,
+            weapon: self.weapon,
+            fabricator: self.fabricator,
+            mining: self.mining,
+            jammer: self.jammer,
+            loadout: self.loadout,
             script: self.script,
             debug: self.debug,
         }
     }
 }
 
+// Optional hardware to mount on the hull, subject to the class module slots
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct Loadout {
+    pub weapon: bool,
+    pub shield: bool,
+    pub mining: bool,
+    pub fabricator: bool,
+    pub jammer: bool,
+    // Warhead replaces the weapon, the ship is the weapon
+    pub warhead: Option<u16>,
+    // Decoys don't need a slot, they're launched out of the hull
+    pub decoys: u8,
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Loadout {
+    pub fn new() -> Self {
+        Self {
+            weapon: true,
+            shield: true,
+            mining: false,
+            fabricator: false,
+            jammer: false,
+            warhead: None,
+            decoys: 0,
+        }
+    }
+
+    pub fn weapon(mut self, weapon: bool) -> Self {
+        self.weapon = weapon;
+        self
+    }
+
+    pub fn shield(mut self, shield: bool) -> Self {
+        self.shield = shield;
+        self
+    }
+
+    pub fn mining(mut self, mining: bool) -> Self {
+        self.mining = mining;
+        self
+    }
+
+    pub fn fabricator(mut self, fabricator: bool) -> Self {
+        self.fabricator = fabricator;
+        self
+    }
+
+    pub fn jammer(mut self, jammer: bool) -> Self {
+        self.jammer = jammer;
+        self
+    }
+
+    pub fn warhead(mut self, damage: u16) -> Self {
+        self.warhead = Some(damage);
+        self
+    }
+
+    pub fn decoys(mut self, decoys: u8) -> Self {
+        self.decoys = decoys;
+        self
+    }
+
+    // Hull only has so many slots, the engine always gets one, then the rest of the modules get
+    // mounted in priority order (radar, weapon, shield, mining, fabricator, jammer)
+    pub fn mount(&self, module_slots: u8) -> Mounts {
+        let mut slots = module_slots.saturating_sub(1);
+        let mut mount = |wanted: bool| {
+            if wanted && slots > 0 {
+                slots -= 1;
+                true
+            } else {
+                false
+            }
+        };
+
+        Mounts {
+            radar: mount(true),
+            weapon: mount(self.weapon && self.warhead.is_none()),
+            shield: mount(self.shield),
+            mining: mount(self.mining),
+            fabricator: mount(self.fabricator),
+            jammer: mount(self.jammer),
+        }
+    }
+}
+
+// Modules that made it onto the hull
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mounts {
+    pub radar: bool,
+    pub weapon: bool,
+    pub shield: bool,
+    pub mining: bool,
+    pub fabricator: bool,
+    pub jammer: bool,
+}
+
 // TODO: For components that are empty (ie tags) can use component ids + insert them from a null ptr
 // This will allow for a list of component ids to make it easier to add/set debug bits on a ship
 // optionally
@@ -375,27 +751,85 @@ impl DebugBuilder {
     }
 }
 
+// Hardware that lives on the ship itself
+fn add_hardware(ship: &mut EntityCommands, loadout: Loadout, mounts: Mounts, rcs: Rcs) {
+    // Weapons
+    if let Some(damage) = loadout.warhead {
+        ship.insert(DebugWarhead { damage });
+    } else if mounts.weapon {
+        ship.insert(DebugWeapon {
+            cooldown: 128,
+            current: 0,
+            damage: 34,
+        })
+        .insert(DebugMissile {
+            cooldown: 128,
+            current: 0,
+        });
+    }
+
+    // Missiles don't get RCS
+    if rcs.lateral_limit > 0 || rcs.axial_limit > 0 {
+        ship.insert(rcs);
+    }
+    if mounts.mining {
+        ship.insert(Mining::default());
+    }
+    if mounts.fabricator {
+        ship.insert(FabricationBay::default());
+    }
+    if mounts.jammer {
+        ship.insert(Jammer::default());
+    }
+    if loadout.decoys > 0 {
+        ship.insert(DecoyLauncher::new(loadout.decoys));
+    }
+}
+
 pub fn add_ship(commands: &mut Commands, ship: StarterShip) -> Entity {
     let radar_heading = ship.radar.heading.0;
     let ship_heading = ship.rotation.heading.0;
-    let ship_translation = ship.movement.position.0.extend(0.);
+    let ship_position = ship.movement.position.0;
+    let ship_translation = ship_position.extend(0.);
     let mut transform = Transform::from_translation(ship_translation);
     transform.rotate(ship_heading.to_quat());
 
+    let stats = ship.class.stats();
+
+    let mounts = ship.loadout.mount(stats.module_slots);
+
     // Probs worth restructuring
     let mut spawned_ship = commands.spawn((transform,));
 
     spawned_ship
-        .insert(Ship(ShipClass::Medium))
+        .insert(Ship(ship.class))
+        .insert(ship.faction)
         .insert(ship.script)
+        .insert(LastAction(ShipAction::new()))
         // Motion components
         .insert(ship.movement)
         .insert(ship.rotation)
+        .insert(Autopilot::default())
         // Health
         .insert(ship.health)
-        // TODO: probs want collision groups (ie ship vs missile vs other ships)
-        .insert(Collider::circle(150.0))
-        .insert(CollisionEventsEnabled);
+        .insert(ship.power)
+        .insert(Cargo {
+            current: ship.cargo.min(stats.cargo_capacity),
+            capacity: stats.cargo_capacity,
+        })
+        // Avian only picks up the events flag when it lands together with the collider
+        .insert((
+            Collider::circle(stats.collider_radius),
+            ship_layers(ship.class, ship.faction),
+            CollisionEventsEnabled,
+        ));
+
+    if let Some(propellant) = ship.propellant {
+        spawned_ship.insert(propellant);
+    }
+    if let Some(reactor) = ship.reactor {
+        spawned_ship.insert(reactor);
+    }
 
     // Ship debug
     if let Some(mov) = ship.debug.mov {
@@ -408,53 +842,131 @@ pub fn add_ship(commands: &mut Commands, ship: StarterShip) -> Entity {
         spawned_ship.insert(health);
     }
 
-    // Weapons
-    if let Some(warhead) = ship.warhead {
-        spawned_ship.insert(warhead);
-    } else {
-        spawned_ship
-            .insert(DebugWeapon {
-                cooldown: 128,
-                current: 0,
-                damage: 34,
-            })
-            .insert(DebugMissile {
-                cooldown: 128,
-                current: 0,
-            });
-    }
+    add_hardware(&mut spawned_ship, ship.loadout, mounts, ship.rcs);
 
     let ship_id = spawned_ship.id();
 
-    // Radar
-    let radar_offset = Vec2::new(0., -20.).extend(1.);
-    let mut radar_transform = Transform::from_translation(ship_translation + radar_offset);
-    radar_transform.rotate(radar_heading.to_quat());
-
-    let mut ship_radar = commands.spawn((
-        radar_transform,
-        ship.radar,
-        AttachedTo(ship_id),
-        AttachOffset(radar_offset),
-    ));
-
-    if let Some(radar) = ship.debug.radar {
-        ship_radar.insert(radar);
+    // Modules for the hardware that lives on the ship itself
+    commands.spawn((ship.engine, AttachedTo(ship_id)));
+    if mounts.weapon {
+        commands.spawn((ship.weapon, AttachedTo(ship_id)));
     }
-    if let Some(arc) = ship.debug.radar_arc {
-        ship_radar.insert(arc);
+    if mounts.mining {
+        commands.spawn((ship.mining, AttachedTo(ship_id)));
+    }
+    if mounts.fabricator {
+        commands.spawn((ship.fabricator, AttachedTo(ship_id)));
+    }
+    if mounts.jammer {
+        commands.spawn((ship.jammer, AttachedTo(ship_id)));
     }
 
-    // Shielding
-    let mut ship_shield = commands.spawn((ship.shield, AttachedTo(ship_id)));
-
-    if let Some(shield_health) = ship.debug.shield_health {
-        ship_shield.insert(shield_health);
+    // Radar
+    if mounts.radar {
+        let radar_offset = Vec2::new(0., -20.).extend(1.);
+        let mut radar_transform = Transform::from_translation(ship_translation + radar_offset);
+        radar_transform.rotate(radar_heading.to_quat());
+
+        let mut ship_radar = commands.spawn((
+            radar_transform,
+            ship.radar,
+            AttachedTo(ship_id),
+            AttachOffset(radar_offset),
+        ));
+
+        if let Some(radar) = ship.debug.radar {
+            ship_radar.insert(radar);
+        }
+        if let Some(arc) = ship.debug.radar_arc {
+            ship_radar.insert(arc);
+        }
     }
-    if let Some(arc) = ship.debug.shield_arc {
-        ship_shield.insert(arc);
+
+    // Shielding
+    if mounts.shield {
+        // The shield sensor is a physics attachment so it gets its own position
+        let shield_rotation = ship.shield.heading.0.to_rotation();
+        let mut ship_shield = commands.spawn((
+            ship.shield,
+            AttachedTo(ship_id),
+            Position(ship_position),
+            shield_rotation,
+            shield_layers(ship.faction),
+        ));
+
+        if let Some(shield_health) = ship.debug.shield_health {
+            ship_shield.insert(shield_health);
+        }
+        if let Some(arc) = ship.debug.shield_arc {
+            ship_shield.insert(arc);
+        }
     }
 
     // Return the entity id of the ship that just got spawned
     ship_id
 }
+
+#[test]
+fn test_loadout_mount() {
+    // Everything fits on a large hull
+    let mounts = Loadout::new().mining(true).fabricator(true).mount(6);
+    assert!(mounts.radar && mounts.weapon && mounts.shield && mounts.mining && mounts.fabricator);
+
+    // Small hull runs out of slots, the lower priority modules gets dropped
+    let mounts = Loadout::new().mining(true).mount(3);
+    assert!(mounts.radar && mounts.weapon);
+    assert!(!mounts.shield && !mounts.mining);
+
+    // Skipping modules frees up the slot
+    let mounts = Loadout::new()
+        .weapon(false)
+        .shield(false)
+        .mining(true)
+        .mount(3);
+    assert!(mounts.radar && mounts.mining);
+
+    // Warhead takes the place of the weapon
+    let mounts = Loadout::new().warhead(100).mount(3);
+    assert!(!mounts.weapon && mounts.shield);
+
+    // Jammer is last in line
+    let mounts = Loadout::new().jammer(true).mount(5);
+    assert!(mounts.shield && mounts.jammer);
+    let mounts = Loadout::new().mining(true).jammer(true).mount(5);
+    assert!(mounts.mining && !mounts.jammer);
+}
+
+#[test]
+fn test_builder_class_order() {
+    use crate::testing::idle;
+
+    // Overrides stick no matter where the class is set
+    let before = ShipBuilder::new(idle())
+        .class(ShipClass::Tiny)
+        .health(42)
+        .velocity_limit(900)
+        .build();
+    let after = ShipBuilder::new(idle())
+        .health(42)
+        .velocity_limit(900)
+        .class(ShipClass::Tiny)
+        .build();
+    for ship in [before, after] {
+        assert_eq!(ship.health.maximum, 42);
+        assert_eq!(ship.movement.thrust.velocity_limit, 900);
+        // Everything else comes from the class
+        let stats = ShipClass::Tiny.stats();
+        assert_eq!(ship.rotation.target.limit, stats.rotation_limit);
+        assert_eq!(ship.rcs.lateral_limit, stats.rcs_limit);
+    }
+}
+
+#[test]
+fn test_builder_faction() {
+    use crate::testing::idle;
+
+    let faction = |faction| ShipBuilder::new(idle()).faction(faction).build().faction;
+    assert_eq!(faction(3), Faction(3));
+    assert_eq!(faction(MAX_FACTIONS - 1), Faction(MAX_FACTIONS - 1));
+    assert_eq!(faction(u8::MAX), Faction(MAX_FACTIONS - 1));
+}
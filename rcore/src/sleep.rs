@@ -0,0 +1,152 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::FixedGameSystem;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::time::Ticks;
+
+// Ship sleep subsystem:
+// - A ship can put itself to sleep, the radar and the computer (script) are off till it wakes up,
+//   the engine is shut down so it coasts along its current velocity.
+// - Wakes up once the timer runs out, or an enemy ship gets within the proximity range, whichever
+//   comes first.
+// - Useful for missiles, accelerate with some fuel then sleep till close to the enemy.
+pub struct SleepPlugin;
+impl Plugin for SleepPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<WakeMessage>()
+            .add_systems(FixedUpdate, apply_sleep.in_set(FixedGameSystem::GameLogic));
+    }
+}
+
+// Needs at least one way to wake up, so there is no empty constructor
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Sleep {
+    // Ticks to sleep for
+    timer: Option<u64>,
+    // Distance to an enemy ship that wakes the ship up
+    proximity: Option<i64>,
+    // Tick the ship went to sleep
+    pub(crate) since: u64,
+}
+
+impl Sleep {
+    // Sleep for a number of ticks
+    pub fn timer(ticks: u64) -> Self {
+        Self {
+            timer: Some(ticks),
+            proximity: None,
+            since: 0,
+        }
+    }
+
+    // Sleep till an enemy ship gets within the distance
+    pub fn proximity(distance: i64) -> Self {
+        Self {
+            timer: None,
+            proximity: Some(distance),
+            since: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn or_timer(mut self, ticks: u64) -> Self {
+        self.timer = Some(ticks);
+        self
+    }
+
+    #[must_use]
+    pub fn or_proximity(mut self, distance: i64) -> Self {
+        self.proximity = Some(distance);
+        self
+    }
+}
+
+// 0 - Ship that woke up
+#[derive(Message, Copy, Clone, Debug)]
+pub struct WakeMessage(pub Entity);
+
+#[expect(clippy::needless_pass_by_value)]
+pub(crate) fn apply_sleep(
+    mut commands: Commands,
+    ticks: Res<Ticks>,
+    mut wake: MessageWriter<WakeMessage>,
+    query: Query<(Entity, &Sleep, &Position, &Faction)>,
+    ship_query: Query<(&Position, &Faction), With<Ship>>,
+) {
+    for (entity, sleep, pos, faction) in query.iter() {
+        let timer = sleep
+            .timer
+            .is_some_and(|timer| ticks.is_ready(sleep.since, timer));
+
+        let base = pos.0.as_ivec2().as_i64vec2();
+        let proximity = sleep.proximity.is_some_and(|distance| {
+            ship_query.iter().any(|(target_pos, target_faction)| {
+                target_faction != faction
+                    && base.distance_squared(target_pos.0.as_ivec2().as_i64vec2())
+                        <= distance.pow(2)
+            })
+        });
+
+        if timer || proximity {
+            commands.entity(entity).remove::<Sleep>();
+            wake.write(WakeMessage(entity));
+        }
+    }
+}
+
+#[test]
+fn test_apply_sleep() {
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    use crate::TICK_HZ;
+    use crate::ship::ShipClass;
+    use crate::time::TimeControlPlugin;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TimeControlPlugin))
+        .add_message::<WakeMessage>()
+        .add_systems(FixedUpdate, apply_sleep)
+        .insert_resource(Time::<Fixed>::from_hz(f64::from(TICK_HZ)))
+        // One tick per update
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / f64::from(TICK_HZ),
+        )));
+
+    let ship = |x: f32, faction: u8| {
+        (
+            Ship(ShipClass::Medium),
+            Position(Vec2::new(x, 0.)),
+            Faction(faction),
+        )
+    };
+    let enemy = app.world_mut().spawn(ship(0., 1)).id();
+    let timer = app
+        .world_mut()
+        .spawn((ship(5000., 0), Sleep::timer(10)))
+        .id();
+    // Sitting on top of a friendly, only enemies wake it up
+    let proximity = app
+        .world_mut()
+        .spawn((ship(3000., 0), Sleep::proximity(1000)))
+        .id();
+    app.world_mut().spawn(ship(3000., 0));
+
+    let now = |app: &App| app.world().resource::<Ticks>().now();
+    while now(&app) < 9 {
+        app.update();
+    }
+    assert!(app.world().get::<Sleep>(timer).is_some());
+    while now(&app) < 10 {
+        app.update();
+    }
+    assert!(app.world().get::<Sleep>(timer).is_none());
+
+    // Enemy closes in
+    assert!(app.world().get::<Sleep>(proximity).is_some());
+    app.world_mut().get_mut::<Position>(enemy).expect("enemy").0 = Vec2::new(2000., 0.);
+    app.update();
+    assert!(app.world().get::<Sleep>(proximity).is_none());
+}
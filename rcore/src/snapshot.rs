@@ -0,0 +1,539 @@
+use avian2d::prelude::Position;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use std::path::PathBuf;
+
+use crate::ARENA_SIZE;
+use crate::attach::AttachedTo;
+use crate::destruction::Wreck;
+use crate::effect::Effect;
+use crate::effect::EffectKind;
+use crate::math::AbsRot;
+use crate::math::FP_SCALE;
+use crate::radar::ArcWidth;
+use crate::radar::Radar;
+use crate::radar::RadarRange;
+use crate::rotation::Heading;
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::style::HULL_STROKE;
+use crate::style::WRECK_STROKE;
+use crate::style::faction_color;
+use crate::style::hull_outline;
+use crate::time::Ticks;
+use crate::weapon::Health;
+use crate::weapon::Shield;
+
+// Snapshot export subsystem:
+// - Writes the current sim state out as an SVG picture plus a JSON dump of the same data, no GPU
+//   needed so headless runs and CI can attach a visual of what happened.
+// - Arena bounds, ships oriented by their heading, wrecks, radar and shield arcs (same edges
+//   `within_arc` checks against), beam, blast and shield impact effects.
+// - Written every N ticks if `SnapshotExport` has an interval, or on demand with a
+//   `SnapshotMessage`. Files are named after the tick, `snapshot_<tick>.svg` and `.json`.
+pub struct SnapshotPlugin;
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<SnapshotMessage>()
+            // After all of the damage for the tick has been dealt
+            .add_systems(FixedLast, process_snapshot_interval)
+            .add_systems(Last, process_snapshot_request);
+    }
+}
+
+// Where to write the snapshots to
+#[derive(Resource, Debug, Clone)]
+pub struct SnapshotExport {
+    pub dir: PathBuf,
+    // Ticks between the snapshots, None only writes on demand
+    pub every: Option<u64>,
+}
+
+// Write a snapshot of the current tick
+#[derive(Message, Copy, Clone, Debug)]
+pub struct SnapshotMessage;
+
+// Pixel width of the picture, the height follows the arena aspect
+const SVG_WIDTH: f32 = 1024.0;
+// Room around the arena bounds
+const SVG_PADDING: f32 = 1.05;
+
+// SVG is y down, flip it so the picture looks like the render
+fn svg_point(pos: Vec2) -> String {
+    format!("{:.0} {:.0}", pos.x, 0.0 - pos.y)
+}
+
+// Headings are clockwise from +Y, same as the SVG rotation once y is flipped
+fn svg_degrees(heading: AbsRot) -> f32 {
+    f32::from(heading.0) * 360.0 / 256.0
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShipSnapshot {
+    pub entity: Entity,
+    pub class: ShipClass,
+    pub faction: Faction,
+    pub pos: Vec2,
+    pub heading: AbsRot,
+    // (current, maximum)
+    pub health: Option<(u16, u16)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WreckSnapshot {
+    pub class: ShipClass,
+    pub pos: Vec2,
+    pub heading: AbsRot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcKind {
+    Radar,
+    Shield,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArcSnapshot {
+    pub kind: ArcKind,
+    pub faction: Faction,
+    // Ship the arc is attached to
+    pub base: Vec2,
+    pub heading: AbsRot,
+    pub arc: u8,
+    pub radius: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EffectSnapshot {
+    pub kind: EffectKind,
+    pub progress: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub ships: Vec<ShipSnapshot>,
+    pub wrecks: Vec<WreckSnapshot>,
+    pub arcs: Vec<ArcSnapshot>,
+    pub effects: Vec<EffectSnapshot>,
+}
+
+// Wedge from the base out to the arc edges, sweeping ccw edge to cw edge
+fn arc_path(base: Vec2, heading: AbsRot, arc: u8, radius: i64) -> String {
+    let edge =
+        |rot: AbsRot| base + (rot.to_heading_fp().as_i64vec2() * radius / FP_SCALE).as_vec2();
+    let ccw = edge(heading.ccw_edge(arc));
+    let cw = edge(heading.cw_edge(arc));
+    // Over half of the circle (arc is the half width)
+    let large = u8::from(arc > 64);
+    format!(
+        "M {} L {} A {radius} {radius} 0 {large} 1 {} Z",
+        svg_point(base),
+        svg_point(ccw),
+        svg_point(cw),
+    )
+}
+
+impl Snapshot {
+    pub fn to_svg(&self) -> String {
+        let extent = ARENA_SIZE * SVG_PADDING;
+        let height = SVG_WIDTH * extent.y / extent.x;
+        let mut svg = vec![
+            format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SVG_WIDTH:.0}\" \
+                 height=\"{height:.0}\" viewBox=\"{:.0} {:.0} {:.0} {:.0}\">",
+                -extent.x / 2.0,
+                -extent.y / 2.0,
+                extent.x,
+                extent.y,
+            ),
+            format!("<title>Tick {}</title>", self.tick),
+            format!(
+                "<rect x=\"{:.0}\" y=\"{:.0}\" width=\"{:.0}\" height=\"{:.0}\" fill=\"#262626\" \
+                 stroke=\"red\" stroke-width=\"10\"/>",
+                -ARENA_SIZE.x / 2.0,
+                -ARENA_SIZE.y / 2.0,
+                ARENA_SIZE.x,
+                ARENA_SIZE.y,
+            ),
+        ];
+
+        // Arcs under the hulls
+        for arc in &self.arcs {
+            let (fill, opacity) = match arc.kind {
+                ArcKind::Radar => (faction_color(arc.faction).to_hex(), 0.15),
+                ArcKind::Shield => ("aqua".to_owned(), 0.3),
+            };
+            svg.push(format!(
+                "<path d=\"{}\" fill=\"{fill}\" fill-opacity=\"{opacity}\" stroke=\"{fill}\" \
+                 stroke-width=\"10\"/>",
+                arc_path(arc.base, arc.heading, arc.arc, arc.radius),
+            ));
+        }
+
+        let hull = |class: ShipClass, pos: Vec2, heading: AbsRot, paint: &str| {
+            let points: Vec<String> = hull_outline(class).iter().copied().map(svg_point).collect();
+            format!(
+                "<polygon points=\"{}\" transform=\"translate({}) rotate({:.1})\" {paint}/>",
+                points.join(" "),
+                svg_point(pos).replace(' ', ","),
+                svg_degrees(heading),
+            )
+        };
+        for wreck in &self.wrecks {
+            svg.push(hull(
+                wreck.class,
+                wreck.pos,
+                wreck.heading,
+                &format!(
+                    "fill=\"none\" stroke=\"{}\" stroke-width=\"10\"",
+                    WRECK_STROKE.to_hex()
+                ),
+            ));
+        }
+        for ship in &self.ships {
+            svg.push(hull(
+                ship.class,
+                ship.pos,
+                ship.heading,
+                &format!(
+                    "fill=\"{}\" stroke=\"{}\" stroke-width=\"10\"",
+                    faction_color(ship.faction).to_hex(),
+                    HULL_STROKE.to_hex()
+                ),
+            ));
+        }
+
+        for effect in &self.effects {
+            let fade = 1.0 - effect.progress;
+            svg.push(match effect.kind {
+                EffectKind::Beam { origin, target } => format!(
+                    "<line x1=\"{:.0}\" y1=\"{:.0}\" x2=\"{:.0}\" y2=\"{:.0}\" stroke=\"red\" \
+                     stroke-width=\"40\" stroke-opacity=\"{fade:.2}\"/>",
+                    origin.x,
+                    0.0 - origin.y,
+                    target.x,
+                    0.0 - target.y,
+                ),
+                EffectKind::Blast { origin, radius } => format!(
+                    "<circle cx=\"{:.0}\" cy=\"{:.0}\" r=\"{radius}\" fill=\"orange\" \
+                     fill-opacity=\"{:.2}\" stroke=\"orange\" stroke-width=\"20\" \
+                     stroke-opacity=\"{fade:.2}\"/>",
+                    origin.x,
+                    0.0 - origin.y,
+                    0.25 * fade,
+                ),
+                EffectKind::ShieldImpact { pos, .. } => format!(
+                    "<circle cx=\"{:.0}\" cy=\"{:.0}\" r=\"60\" fill=\"aqua\" \
+                     fill-opacity=\"{fade:.2}\"/>",
+                    pos.x,
+                    0.0 - pos.y,
+                ),
+            });
+        }
+
+        svg.push("</svg>".to_owned());
+        svg.join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        let ships: Vec<String> = self
+            .ships
+            .iter()
+            .map(|ship| {
+                let health = ship.health.map_or_else(
+                    || "null".to_owned(),
+                    |(current, maximum)| format!("{{\"current\":{current},\"maximum\":{maximum}}}"),
+                );
+                format!(
+                    "{{\"entity\":\"{}\",\"class\":\"{:?}\",\"faction\":{},\"x\":{:.0},\"y\":{:.0},\
+                     \"heading\":{},\"health\":{health}}}",
+                    ship.entity, ship.class, ship.faction.0, ship.pos.x, ship.pos.y, ship.heading.0,
+                )
+            })
+            .collect();
+
+        let wrecks: Vec<String> = self
+            .wrecks
+            .iter()
+            .map(|wreck| {
+                format!(
+                    "{{\"class\":\"{:?}\",\"x\":{:.0},\"y\":{:.0},\"heading\":{}}}",
+                    wreck.class, wreck.pos.x, wreck.pos.y, wreck.heading.0,
+                )
+            })
+            .collect();
+
+        let arcs: Vec<String> = self
+            .arcs
+            .iter()
+            .map(|arc| {
+                format!(
+                    "{{\"kind\":\"{:?}\",\"faction\":{},\"x\":{:.0},\"y\":{:.0},\"heading\":{},\
+                     \"arc\":{},\"radius\":{}}}",
+                    arc.kind,
+                    arc.faction.0,
+                    arc.base.x,
+                    arc.base.y,
+                    arc.heading.0,
+                    arc.arc,
+                    arc.radius,
+                )
+            })
+            .collect();
+
+        let effects: Vec<String> = self
+            .effects
+            .iter()
+            .map(|effect| {
+                let kind = match effect.kind {
+                    EffectKind::Beam { origin, target } => format!(
+                        "\"kind\":\"Beam\",\"x\":{:.0},\"y\":{:.0},\"target_x\":{:.0},\
+                         \"target_y\":{:.0}",
+                        origin.x, origin.y, target.x, target.y,
+                    ),
+                    EffectKind::Blast { origin, radius } => format!(
+                        "\"kind\":\"Blast\",\"x\":{:.0},\"y\":{:.0},\"radius\":{radius}",
+                        origin.x, origin.y,
+                    ),
+                    EffectKind::ShieldImpact { pos, .. } => format!(
+                        "\"kind\":\"ShieldImpact\",\"x\":{:.0},\"y\":{:.0}",
+                        pos.x, pos.y,
+                    ),
+                };
+                format!("{{{kind},\"progress\":{:.2}}}", effect.progress)
+            })
+            .collect();
+
+        format!(
+            "{{\"tick\":{},\"ships\":[{}],\"wrecks\":[{}],\"arcs\":[{}],\"effects\":[{}]}}",
+            self.tick,
+            ships.join(","),
+            wrecks.join(","),
+            arcs.join(","),
+            effects.join(","),
+        )
+    }
+}
+
+// Everything that goes into a snapshot
+#[derive(SystemParam)]
+#[expect(clippy::type_complexity)]
+struct SnapshotQuery<'w, 's> {
+    ticks: Res<'w, Ticks>,
+    ship_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Ship,
+            &'static Faction,
+            &'static Position,
+            &'static Heading,
+            Option<&'static Health>,
+        ),
+    >,
+    wreck_query: Query<'w, 's, (&'static Wreck, &'static Position, Option<&'static Heading>)>,
+    arc_query: Query<
+        'w,
+        's,
+        (
+            &'static Heading,
+            &'static ArcWidth,
+            &'static AttachedTo,
+            Option<&'static RadarRange>,
+            Option<&'static Shield>,
+            Has<Radar>,
+        ),
+    >,
+    parent_query: Query<'w, 's, (&'static Position, &'static Faction)>,
+    effect_query: Query<'w, 's, &'static Effect>,
+}
+
+impl SnapshotQuery<'_, '_> {
+    fn capture(&self) -> Snapshot {
+        let mut ships: Vec<ShipSnapshot> = self
+            .ship_query
+            .iter()
+            .map(
+                |(entity, ship, faction, pos, heading, health)| ShipSnapshot {
+                    entity,
+                    class: ship.0,
+                    faction: *faction,
+                    pos: pos.0,
+                    heading: heading.0,
+                    health: health.map(|health| (health.current, health.maximum)),
+                },
+            )
+            .collect();
+        // Stable order for the diffs
+        ships.sort_by_key(|ship| ship.entity);
+
+        let wrecks = self
+            .wreck_query
+            .iter()
+            .map(|(wreck, pos, heading)| WreckSnapshot {
+                class: wreck.0,
+                pos: pos.0,
+                heading: heading.map_or(AbsRot(0), |heading| heading.0),
+            })
+            .collect();
+
+        let mut arcs: Vec<(Entity, ArcSnapshot)> = self
+            .arc_query
+            .iter()
+            .filter_map(|(heading, arc, attached_to, range, shield, is_radar)| {
+                let (kind, radius) = match (range, shield) {
+                    (Some(range), _) if is_radar => (ArcKind::Radar, range.0),
+                    (_, Some(shield)) => (ArcKind::Shield, shield.radius()),
+                    _ => return None,
+                };
+                let (pos, faction) = self.parent_query.get(attached_to.0).ok()?;
+                Some((
+                    attached_to.0,
+                    ArcSnapshot {
+                        kind,
+                        faction: *faction,
+                        base: pos.0,
+                        heading: heading.0,
+                        arc: arc.current,
+                        radius,
+                    },
+                ))
+            })
+            .collect();
+        arcs.sort_by_key(|(entity, arc)| (*entity, arc.kind == ArcKind::Shield));
+
+        let effects = self
+            .effect_query
+            .iter()
+            .map(|effect| EffectSnapshot {
+                kind: effect.kind,
+                progress: effect.progress(&self.ticks),
+            })
+            .collect();
+
+        Snapshot {
+            tick: self.ticks.now(),
+            ships,
+            wrecks,
+            arcs: arcs.into_iter().map(|(_, arc)| arc).collect(),
+            effects,
+        }
+    }
+}
+
+fn write_snapshot(export: &SnapshotExport, snapshot: &Snapshot) {
+    let path = export.dir.join(format!("snapshot_{:08}", snapshot.tick));
+    let written = std::fs::create_dir_all(&export.dir)
+        .and_then(|()| std::fs::write(path.with_extension("svg"), snapshot.to_svg()))
+        .and_then(|()| std::fs::write(path.with_extension("json"), snapshot.to_json()));
+    match written {
+        Ok(()) => println!("Snapshot - {}", path.with_extension("svg").display()),
+        Err(err) => println!("ERROR - SNAPSHOT - {err}"),
+    }
+}
+
+// On the exact tick, however many ticks the frame runs
+fn process_snapshot_interval(export: Option<Res<SnapshotExport>>, snapshot: SnapshotQuery) {
+    if let Some(export) = export
+        && let Some(every) = export.every
+        && snapshot.ticks.now().is_multiple_of(every)
+    {
+        write_snapshot(&export, &snapshot.capture());
+    }
+}
+
+// Once the ticks for the frame are done, works while paused
+fn process_snapshot_request(
+    export: Option<Res<SnapshotExport>>,
+    mut messages: MessageReader<SnapshotMessage>,
+    snapshot: SnapshotQuery,
+) {
+    let requested = messages.read().count() > 0;
+    if let Some(export) = export
+        && requested
+    {
+        write_snapshot(&export, &snapshot.capture());
+    }
+}
+
+#[test]
+fn test_arc_path() {
+    // Facing north with a half circle arc, west edge around to the east edge
+    assert_eq!(
+        arc_path(Vec2::ZERO, AbsRot(0), 64, 100),
+        "M 0 0 L -100 0 A 100 100 0 0 1 100 0 Z"
+    );
+
+    // Facing east with a narrow arc, y is flipped for the SVG
+    assert_eq!(
+        arc_path(Vec2::new(10., 20.), AbsRot(64), 32, 100),
+        "M 10 -20 L 80 -90 A 100 100 0 0 1 80 50 Z"
+    );
+
+    // Wide arcs take the long way around
+    assert!(arc_path(Vec2::ZERO, AbsRot(0), 96, 100).contains(" 0 1 1 "));
+}
+
+#[test]
+fn test_snapshot_export() {
+    use crate::ship::ShipBuilder;
+    use crate::ship::add_ship;
+    use crate::testing::idle;
+    use crate::testing::sim_app;
+
+    let dir = std::env::temp_dir().join(format!("rcore_snapshot_{}", std::process::id()));
+    let mut app = sim_app();
+    app.insert_resource(SnapshotExport {
+        dir: dir.clone(),
+        every: Some(16),
+    });
+    let ships = [(0, -1000), (1, 1000)].map(|(faction, y)| {
+        let ship = ShipBuilder::new(idle())
+            .faction(faction)
+            .position(0, y)
+            .build();
+        add_ship(&mut app.world_mut().commands(), ship)
+    });
+
+    let now = |app: &App| app.world().resource::<Ticks>().now();
+    while now(&app) < 16 {
+        app.update();
+    }
+    let read = |tick: u64, extension: &str| {
+        std::fs::read_to_string(dir.join(format!("snapshot_{tick:08}.{extension}")))
+            .expect("snapshot")
+    };
+    // Only on the interval
+    assert!(!dir.join("snapshot_00000008.svg").exists());
+
+    let svg = read(16, "svg");
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("<title>Tick 16</title>"));
+    assert_eq!(svg.matches("<polygon").count(), 2);
+    for faction in [0, 1] {
+        assert!(svg.contains(&faction_color(Faction(faction)).to_hex()));
+    }
+
+    // Ships in entity order with their radar and shield arcs
+    let json = read(16, "json");
+    assert!(json.starts_with("{\"tick\":16,\"ships\":[{\"entity\":"));
+    let mut sorted = ships;
+    sorted.sort();
+    let [first, second] = sorted.map(|ship| json.find(&format!("\"{ship}\"")).expect("ship"));
+    assert!(first < second);
+    assert_eq!(json.matches("\"kind\":\"Radar\"").count(), 2);
+    assert_eq!(json.matches("\"kind\":\"Shield\"").count(), 2);
+
+    // On demand, off the interval
+    app.world_mut().write_message(SnapshotMessage);
+    app.update();
+    let tick = now(&app);
+    assert!(!tick.is_multiple_of(16));
+    assert!(read(tick, "json").starts_with(&format!("{{\"tick\":{tick},")));
+
+    std::fs::remove_dir_all(&dir).expect("cleanup");
+}
@@ -0,0 +1,355 @@
+use bevy::prelude::*;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::ship::Faction;
+use crate::ship::Ship;
+use crate::ship::ShipClass;
+use crate::time::Ticks;
+
+// Combat event and match statistics subsystem:
+// - Every shot, hit, shield absorption, kill, launch and collision is emitted as a `SimEvent`
+//   with the tick, source, target and amount, for anything that wants to follow the fight.
+// - The match stats aggregates the events into per-ship and per-faction stats (accuracy, damage
+//   dealt and taken, kills, survival time).
+// - At match end (`MatchEndMessage` or app exit) the stats are dumped as JSON if a `StatsDump`
+//   path was configured.
+pub struct StatsPlugin;
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<SimEvent>()
+            .add_message::<MatchEndMessage>()
+            .init_resource::<MatchStats>()
+            // After all of the damage for the tick has been dealt
+            .add_systems(FixedLast, apply_match_stats)
+            .add_systems(Last, process_match_end);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimEventKind {
+    // Weapon fired or warhead went off, amount is the damage it carries
+    Shot,
+    // Damage that made it onto the hull, no source for ramming and reactor blasts
+    Hit,
+    // Damage soaked up by the target shield
+    ShieldAbsorb,
+    // Target destroyed, amount is the damage of the killing blow, no source for ramming and reactor
+    // blasts
+    Kill,
+    // Missile launched
+    Launch,
+    // Ships ran into each other, amount is the impact damage taken by the source
+    Collision,
+}
+
+#[derive(Message, Copy, Clone, Debug)]
+pub struct SimEvent {
+    pub tick: u64,
+    pub kind: SimEventKind,
+    pub source: Option<Entity>,
+    pub target: Option<Entity>,
+    pub amount: u32,
+}
+
+impl SimEvent {
+    pub fn new(
+        tick: u64,
+        kind: SimEventKind,
+        source: Option<Entity>,
+        target: Option<Entity>,
+        amount: u32,
+    ) -> Self {
+        Self {
+            tick,
+            kind,
+            source,
+            target,
+            amount,
+        }
+    }
+}
+
+// Ends the match, triggers the stats dump
+#[derive(Message, Copy, Clone, Debug)]
+pub struct MatchEndMessage;
+
+// Where to write the stats to at match end
+#[derive(Resource, Debug, Clone)]
+pub struct StatsDump(pub PathBuf);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CombatStats {
+    pub shots: u32,
+    pub hits: u32,
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub shield_absorbed: u64,
+    pub kills: u32,
+    pub launches: u32,
+    pub collisions: u32,
+}
+
+impl CombatStats {
+    // Hits per shot, warheads can hit several ships so this can go over 1
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            self.hits as f32 / self.shots as f32
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.shots += other.shots;
+        self.hits += other.hits;
+        self.damage_dealt += other.damage_dealt;
+        self.damage_taken += other.damage_taken;
+        self.shield_absorbed += other.shield_absorbed;
+        self.kills += other.kills;
+        self.launches += other.launches;
+        self.collisions += other.collisions;
+    }
+
+    fn json_fields(&self) -> String {
+        format!(
+            "\"shots\":{},\"hits\":{},\"accuracy\":{:.3},\"damage_dealt\":{},\"damage_taken\":{},\
+             \"shield_absorbed\":{},\"kills\":{},\"launches\":{},\"collisions\":{}",
+            self.shots,
+            self.hits,
+            self.accuracy(),
+            self.damage_dealt,
+            self.damage_taken,
+            self.shield_absorbed,
+            self.kills,
+            self.launches,
+            self.collisions,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShipStats {
+    pub class: ShipClass,
+    pub faction: Faction,
+    pub spawned: u64,
+    pub destroyed: bool,
+    // Tick the ship left the field (destroyed or expended)
+    pub despawned: Option<u64>,
+    pub combat: CombatStats,
+}
+
+impl ShipStats {
+    pub fn survival(&self, now: u64) -> u64 {
+        self.despawned.unwrap_or(now).saturating_sub(self.spawned)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FactionStats {
+    pub ships: u32,
+    pub lost: u32,
+    // Total ticks survived across the faction ships
+    pub survival: u64,
+    pub combat: CombatStats,
+}
+
+// Aggregated stats for the whole match, keyed by entity to preserve replay orders
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MatchStats {
+    pub ships: BTreeMap<Entity, ShipStats>,
+}
+
+impl MatchStats {
+    pub fn spawn(&mut self, ship: Entity, class: ShipClass, faction: Faction, tick: u64) {
+        self.ships.insert(
+            ship,
+            ShipStats {
+                class,
+                faction,
+                spawned: tick,
+                destroyed: false,
+                despawned: None,
+                combat: CombatStats::default(),
+            },
+        );
+    }
+
+    pub fn despawn(&mut self, ship: Entity, tick: u64) {
+        if let Some(stats) = self.ships.get_mut(&ship) {
+            stats.despawned.get_or_insert(tick);
+        }
+    }
+
+    pub fn record(&mut self, event: &SimEvent) {
+        let amount = u64::from(event.amount);
+        // Self inflicted damage (own shield, own blast) doesn't count as dealt
+        let credit = event.source != event.target;
+
+        if let Some(source) = event.source.and_then(|source| self.ships.get_mut(&source)) {
+            let combat = &mut source.combat;
+            match event.kind {
+                SimEventKind::Shot => combat.shots += 1,
+                SimEventKind::Hit if credit => {
+                    combat.hits += 1;
+                    combat.damage_dealt += amount;
+                }
+                SimEventKind::ShieldAbsorb if credit => combat.damage_dealt += amount,
+                SimEventKind::Kill if credit => combat.kills += 1,
+                SimEventKind::Launch => combat.launches += 1,
+                SimEventKind::Collision => combat.collisions += 1,
+                _ => (),
+            }
+        }
+
+        if let Some(target) = event.target.and_then(|target| self.ships.get_mut(&target)) {
+            match event.kind {
+                SimEventKind::Hit => target.combat.damage_taken += amount,
+                SimEventKind::ShieldAbsorb => target.combat.shield_absorbed += amount,
+                SimEventKind::Kill => {
+                    target.destroyed = true;
+                    target.despawned.get_or_insert(event.tick);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    pub fn factions(&self, now: u64) -> BTreeMap<Faction, FactionStats> {
+        let mut factions: BTreeMap<Faction, FactionStats> = BTreeMap::new();
+        for ship in self.ships.values() {
+            let faction = factions.entry(ship.faction).or_default();
+            faction.ships += 1;
+            faction.lost += u32::from(ship.destroyed);
+            faction.survival += ship.survival(now);
+            faction.combat.add(&ship.combat);
+        }
+        factions
+    }
+
+    pub fn to_json(&self, now: u64) -> String {
+        let ships: Vec<String> = self
+            .ships
+            .iter()
+            .map(|(entity, ship)| {
+                format!(
+                    "{{\"entity\":\"{entity}\",\"class\":\"{:?}\",\"faction\":{},\"spawned\":{},\
+                     \"destroyed\":{},\"survival\":{},{}}}",
+                    ship.class,
+                    ship.faction.0,
+                    ship.spawned,
+                    ship.destroyed,
+                    ship.survival(now),
+                    ship.combat.json_fields(),
+                )
+            })
+            .collect();
+
+        let factions: Vec<String> = self
+            .factions(now)
+            .iter()
+            .map(|(faction, stats)| {
+                format!(
+                    "{{\"faction\":{},\"ships\":{},\"lost\":{},\"survival\":{},{}}}",
+                    faction.0,
+                    stats.ships,
+                    stats.lost,
+                    stats.survival,
+                    stats.combat.json_fields(),
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"tick\":{now},\"ships\":[{}],\"factions\":[{}]}}",
+            ships.join(","),
+            factions.join(","),
+        )
+    }
+}
+
+fn apply_match_stats(
+    ticks: Res<Ticks>,
+    mut stats: ResMut<MatchStats>,
+    mut events: MessageReader<SimEvent>,
+    spawned: Query<(Entity, &Ship, &Faction), Added<Ship>>,
+    mut despawned: RemovedComponents<Ship>,
+) {
+    for (entity, ship, faction) in spawned.iter() {
+        stats.spawn(entity, ship.0, *faction, ticks.now());
+    }
+    for event in events.read() {
+        stats.record(event);
+    }
+    for entity in despawned.read() {
+        stats.despawn(entity, ticks.now());
+    }
+}
+
+fn process_match_end(
+    ticks: Res<Ticks>,
+    stats: Res<MatchStats>,
+    dump: Option<Res<StatsDump>>,
+    mut end: MessageReader<MatchEndMessage>,
+    mut exit: MessageReader<AppExit>,
+    mut dumped: Local<bool>,
+) {
+    let ended = end.read().count() > 0 || exit.read().count() > 0;
+    if !ended || *dumped {
+        return;
+    }
+    *dumped = true;
+
+    let Some(dump) = dump else {
+        return;
+    };
+    match std::fs::write(&dump.0, stats.to_json(ticks.now())) {
+        Ok(()) => println!("Match stats - {}", dump.0.display()),
+        Err(err) => println!("ERROR - STATS - {err}"),
+    }
+}
+
+#[test]
+fn test_match_stats() {
+    let ship = Entity::from_raw_u32(1).expect("entity");
+    let enemy = Entity::from_raw_u32(2).expect("entity");
+
+    let mut stats = MatchStats::default();
+    stats.spawn(ship, ShipClass::Medium, Faction(0), 0);
+    stats.spawn(enemy, ShipClass::Small, Faction(1), 0);
+
+    for event in [
+        SimEvent::new(1, SimEventKind::Shot, Some(ship), Some(enemy), 34),
+        SimEvent::new(1, SimEventKind::ShieldAbsorb, Some(ship), Some(enemy), 20),
+        SimEvent::new(1, SimEventKind::Hit, Some(ship), Some(enemy), 14),
+        SimEvent::new(2, SimEventKind::Shot, Some(ship), Some(enemy), 34),
+        SimEvent::new(3, SimEventKind::Hit, Some(ship), Some(enemy), 34),
+        SimEvent::new(3, SimEventKind::Kill, Some(ship), Some(enemy), 34),
+        // Ramming, the damage lands without a source so it's not a hit
+        SimEvent::new(3, SimEventKind::Collision, Some(ship), Some(enemy), 5),
+        SimEvent::new(3, SimEventKind::Hit, None, Some(ship), 5),
+    ] {
+        stats.record(&event);
+    }
+
+    let shooter = stats.ships[&ship];
+    assert_eq!(shooter.combat.shots, 2);
+    assert_eq!(shooter.combat.hits, 2);
+    assert_eq!(shooter.combat.damage_dealt, 68);
+    assert_eq!(shooter.combat.kills, 1);
+    assert_eq!(shooter.combat.collisions, 1);
+    assert_eq!(shooter.combat.damage_taken, 5);
+    assert!((shooter.combat.accuracy() - 1.0).abs() < f32::EPSILON);
+    assert_eq!(shooter.survival(10), 10);
+
+    let target = stats.ships[&enemy];
+    assert_eq!(target.combat.damage_taken, 48);
+    assert_eq!(target.combat.shield_absorbed, 20);
+    assert!(target.destroyed);
+    assert_eq!(target.survival(10), 3);
+
+    let factions = stats.factions(10);
+    assert_eq!(factions[&Faction(1)].lost, 1);
+    assert_eq!(factions[&Faction(0)].combat.kills, 1);
+}
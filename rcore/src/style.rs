@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::ship::Faction;
+use crate::ship::ShipClass;
+
+// Look of the sim, shared by the render and the snapshot export so the two pictures agree:
+// - Every faction gets a team color (cycles past the end of the palette).
+// - Hull outlines per class, nose up (+Y).
+
+// Team colors
+pub const FACTION_PALETTE: [Srgba; 4] = [
+    bevy::color::palettes::css::LIME,
+    bevy::color::palettes::css::TOMATO,
+    bevy::color::palettes::css::DODGER_BLUE,
+    bevy::color::palettes::css::GOLD,
+];
+
+pub const HULL_STROKE: Srgba = bevy::color::palettes::css::BLACK;
+pub const WRECK_STROKE: Srgba = bevy::color::palettes::css::DIM_GRAY;
+
+pub const fn faction_color(faction: Faction) -> Srgba {
+    FACTION_PALETTE[faction.0 as usize % FACTION_PALETTE.len()]
+}
+
+// Hull outlines, nose up (+Y), closed back to the first point
+const LARGE_HULL: [Vec2; 7] = [
+    Vec2::new(0.0, 400.0),
+    Vec2::new(150.0, 200.0),
+    Vec2::new(150.0, -300.0),
+    Vec2::new(250.0, -400.0),
+    Vec2::new(-250.0, -400.0),
+    Vec2::new(-150.0, -300.0),
+    Vec2::new(-150.0, 200.0),
+];
+const MEDIUM_HULL: [Vec2; 4] = [
+    Vec2::new(0.0, 200.0),
+    Vec2::new(100.0, -200.0),
+    Vec2::new(0.0, -100.0),
+    Vec2::new(-100.0, -200.0),
+];
+const SMALL_HULL: [Vec2; 3] = [
+    Vec2::new(0.0, 100.0),
+    Vec2::new(60.0, -75.0),
+    Vec2::new(-60.0, -75.0),
+];
+const TINY_HULL: [Vec2; 3] = [
+    Vec2::new(0.0, 40.0),
+    Vec2::new(15.0, -30.0),
+    Vec2::new(-15.0, -30.0),
+];
+
+pub const fn hull_outline(class: ShipClass) -> &'static [Vec2] {
+    match class {
+        ShipClass::Large => &LARGE_HULL,
+        ShipClass::Medium => &MEDIUM_HULL,
+        ShipClass::Small => &SMALL_HULL,
+        ShipClass::Tiny => &TINY_HULL,
+    }
+}
+
+#[test]
+fn test_faction_color() {
+    assert_eq!(faction_color(Faction(0)), FACTION_PALETTE[0]);
+    assert_eq!(faction_color(Faction(5)), FACTION_PALETTE[1]);
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use std::time::Duration;
+
+use crate::SimulationPlugin;
+use crate::TICK_HZ;
+use crate::script::Script;
+use crate::script::ShipAction;
+use crate::script::ShipCollision;
+use crate::script::ShipScript;
+use crate::script::ShipStatus;
+
+// Shared test fixtures:
+// - `sim_app` is the whole sim without a render, stepping one tick per `App::update`.
+// - `idle` is a ship script that never does anything, for ships that only need to be there.
+
+#[derive(Clone)]
+pub(crate) struct Idle;
+impl ShipScript for Idle {
+    fn on_update(&mut self, _status: &ShipStatus) -> ShipAction {
+        ShipAction::new()
+    }
+    fn on_contact(&mut self, _target_pos: IVec2, _target_entity: Entity) {}
+    fn on_collision(&mut self, _collision: &ShipCollision) {}
+}
+
+pub(crate) fn idle() -> Script {
+    Script {
+        script: Box::new(Idle),
+    }
+}
+
+pub(crate) fn sim_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, SimulationPlugin))
+        // One tick per update
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / f64::from(TICK_HZ),
+        )));
+    // Avian sets up some of its resources on finish
+    app.finish();
+    app.cleanup();
+    app
+}
@@ -18,6 +18,10 @@ pub enum TimeMsg {
 pub struct Ticks(u64);
 
 impl Ticks {
+    pub fn now(&self) -> u64 {
+        self.0
+    }
+
     pub fn elapsed(&self, start: u64) -> u64 {
         self.0.wrapping_sub(start)
     }
@@ -1,21 +1,42 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
 
 use crate::FixedGameSystem;
+use crate::TICK_HZ;
 
+use avian2d::prelude::Collider;
+use avian2d::prelude::ColliderDisabled;
+use avian2d::prelude::CollisionStart;
 use avian2d::prelude::Position;
+use avian2d::prelude::Sensor;
 
+use crate::attach::AttachedTo;
 use crate::attach::Attachments;
+use crate::destruction::Destroyed;
+use crate::effect::Effect;
 use crate::math::FP_SCALE;
+use crate::math::RelRot;
+use crate::math::tick_step;
+use crate::module::Module;
+use crate::module::ModuleDamageEvent;
+use crate::module::ModuleKind;
+use crate::module::split_damage;
+use crate::power::Power;
+use crate::power::apply_power_generation;
 use crate::radar::ArcCheck;
 use crate::radar::ArcWidth;
+use crate::radar::apply_arc_width;
 use crate::radar::within_arc;
 use crate::rotation::Heading;
 use crate::rotation::TargetHeading;
 use crate::script::Script;
+use crate::ship::Faction;
+use crate::ship::Ship;
 use crate::ship::ShipBuilder;
+use crate::ship::ShipClass;
 use crate::spawner::SpawnMessage;
+use crate::stats::SimEvent;
+use crate::stats::SimEventKind;
+use crate::time::Ticks;
 
 use crate::AbsRot;
 
@@ -35,9 +56,19 @@ impl Plugin for WeaponPlugin {
                 (apply_debug_weapon_cooldown, apply_debug_missile_cooldown)
                     .in_set(FixedGameSystem::GameLogic),
             )
+            .add_systems(
+                FixedUpdate,
+                (apply_shield_regen, apply_shield_collider)
+                    .chain()
+                    .after(apply_arc_width)
+                    .after(apply_power_generation)
+                    .in_set(FixedGameSystem::GameLogic),
+            )
             .add_systems(
                 FixedUpdate,
                 (
+                    // Shield gets first dibs on any warhead, before they get to blow up
+                    process_shield_intercept,
                     process_fire_debug_warhead_message,
                     process_fire_debug_weapon_message,
                     // Missile will spawn the next frame
@@ -64,12 +95,33 @@ pub struct HealthDebug;
 
 // 0 - Origin of the damage (for shield coverage check)
 // 1 - health to deduce
+// 2 - who dealt the damage (for the match stats)
+// 3 - what dealt the damage, only the weapons and warheads count as hits
 #[derive(EntityEvent, Copy, Clone, Debug)]
 pub struct DamageEvent {
     #[event_target]
     pub target: Entity,
     pub pos: IVec2,
     pub dmg: u16,
+    pub source: Option<Entity>,
+    pub cause: DamageCause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageCause {
+    Weapon,
+    Warhead,
+    // Ships ramming each other
+    Impact,
+    // Reactor going up with the ship
+    Reactor,
+}
+
+impl DamageCause {
+    // Damage that comes from a weapon shot or a warhead
+    pub fn is_shot(self) -> bool {
+        matches!(self, Self::Weapon | Self::Warhead)
+    }
 }
 
 // Basic 360 no scope test weapon, it can zap anything when told to fire
@@ -109,25 +161,6 @@ pub struct DebugWarhead {
     pub damage: u16,
 }
 
-// New entity + component for rendering the weapon then it fades away
-#[derive(Component)]
-pub struct RenderDebugWeapon {
-    pub origin: Vec2,
-    pub target: Vec2,
-
-    // Persist for this amount of time
-    pub fade: Timer,
-}
-
-// New entity + component for rendering the weapon then it fades away
-#[derive(Component)]
-pub struct RenderDebugWarhead {
-    pub origin: Vec2,
-
-    // Persist for this amount of time
-    pub fade: Timer,
-}
-
 // Weapon Firing event,
 // TODO: probs want to look at some other option but for now we can use an event to fire
 // the weapon
@@ -154,16 +187,30 @@ pub struct FireDebugMissileMessage(pub Entity);
 pub fn process_damage_event(
     trigger: On<DamageEvent>,
     mut commands: Commands,
-    mut query: Query<(&mut Health, &Position, &Attachments), Without<Shield>>,
+    mut query: Query<(&mut Health, &Position, &Heading, Option<&Attachments>), Without<Shield>>,
     mut shield_query: Query<(&mut Health, &Shield, &Heading, &ArcWidth)>,
+    module_query: Query<&Module>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
 ) {
     let ship = trigger.event().target;
-    if let Ok((mut health, ship_pos, attachments)) = query.get_mut(ship) {
+    let source = trigger.event().source;
+    // Only the shots get credited in the match stats, ramming and reactor blasts have no shot to
+    // go with them and would throw off the accuracy
+    let credit = source.filter(|_| trigger.event().cause.is_shot());
+    if let Ok((mut health, ship_pos, ship_heading, attachments)) = query.get_mut(ship) {
+        // Already destroyed earlier this tick, waiting on the despawn
+        if health.current == 0 {
+            return;
+        }
+
         let mut ship_damage: u16 = trigger.event().dmg;
+        let mut shield_hit = None;
 
         // Scan through the attachments to find the shield if there is one.
+        // A ship that got stripped of all of its modules no longer has `Attachments`
         // TODO: support multiple shield, for now assume one.
-        for attachment in attachments.iter() {
+        for attachment in attachments.iter().flat_map(|a| a.iter()) {
             if let Ok((mut shield_health, shield, heading, arc)) = shield_query.get_mut(attachment)
             {
                 // Check if the shield is not at 0 health
@@ -182,22 +229,9 @@ pub fn process_damage_event(
                 ) {
                     ArcCheck::InsideArc => {
                         // Split incoming damage into shield and ship damage
-                        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                        let shield_damage: u16 =
-                            (f32::from(trigger.event().dmg) * shield.damage_reduce).round() as u16;
-
-                        // If shield can't cover full shield damage, deduce and pass on to ship
-                        if let Some(new_shield_health) =
-                            shield_health.current.checked_sub(shield_damage)
-                        {
-                            shield_health.current = new_shield_health;
-                            ship_damage = trigger.event().dmg - shield_damage;
-                        } else {
-                            // Can't cover full damage, deduce what we can and pass it on
-                            let carry_shield_damage = shield_damage - shield_health.current;
-                            shield_health.current = 0;
-                            ship_damage = trigger.event().dmg - shield_damage + carry_shield_damage;
-                        }
+                        ship_damage =
+                            shield.absorb(&mut shield_health, arc.current, trigger.event().dmg);
+                        shield_hit = Some(attachment);
                     }
                     ArcCheck::OutsideArc => {
                         // Pass on full damage
@@ -215,12 +249,69 @@ pub fn process_damage_event(
             }
         }
 
-        if let Some(new_health) = health.current.checked_sub(ship_damage) {
-            health.current = new_health;
-        } else {
-            // This ship is now dead, despawn it
-            println!("Despawning - {ship:?}");
-            commands.entity(ship).despawn();
+        let absorbed = trigger.event().dmg - ship_damage;
+        if absorbed > 0 {
+            if let Some(shield) = shield_hit {
+                commands.spawn(Effect::shield_impact(
+                    &ticks,
+                    shield,
+                    trigger.event().pos.as_vec2(),
+                ));
+            }
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::ShieldAbsorb,
+                credit,
+                Some(ship),
+                u32::from(absorbed),
+            ));
+        }
+        if ship_damage > 0 {
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Hit,
+                credit,
+                Some(ship),
+                u32::from(ship_damage),
+            ));
+        }
+
+        // Hull damage also gets split across the modules mounted on the side of the ship that got
+        // hit
+        if ship_damage > 0
+            && let Some(hit) = AbsRot::from_vec2_angle(ship_pos.0.as_ivec2(), trigger.event().pos)
+        {
+            let exposed: Vec<Entity> = attachments
+                .iter()
+                .flat_map(|a| a.iter())
+                .filter(|attachment| {
+                    module_query
+                        .get(*attachment)
+                        .is_ok_and(|module| module.exposed(ship_heading.0, hit))
+                })
+                .collect();
+            for (target, dmg) in exposed.iter().zip(split_damage(ship_damage, exposed.len())) {
+                if dmg > 0 {
+                    commands.trigger(ModuleDamageEvent {
+                        target: *target,
+                        dmg,
+                    });
+                }
+            }
+        }
+
+        health.current = health.current.saturating_sub(ship_damage);
+        if health.current == 0 {
+            // This ship is now dead, the destruction subsystem takes it from here
+            println!("Destroyed - {ship:?}");
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Kill,
+                credit,
+                Some(ship),
+                u32::from(ship_damage),
+            ));
+            commands.entity(ship).insert(Destroyed(source));
         }
     }
 }
@@ -240,33 +331,76 @@ pub(crate) fn apply_debug_missile_cooldown(mut query: Query<&mut DebugMissile>)
 pub fn process_fire_debug_weapon_message(
     mut commands: Commands,
     mut fire_debug_weapon_message: MessageReader<FireDebugWeaponMessage>,
-    mut query: Query<(&mut DebugWeapon, &Position)>,
-    position: Query<&Transform>,
+    // Ships that got destroyed earlier in the tick don't get their last shot off
+    mut query: Query<(&mut DebugWeapon, &Position, Option<&Attachments>), Without<Destroyed>>,
+    position: Query<(&Transform, &Position)>,
+    mut shield_query: Query<(&mut Health, &Shield, &Heading, &ArcWidth)>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
 ) {
     for FireDebugWeaponMessage(ship, target) in fire_debug_weapon_message.read() {
-        if let Ok((mut weapon, ship_pos)) = query.get_mut(*ship)
+        if let Ok((mut weapon, ship_pos, attachments)) = query.get_mut(*ship)
             && weapon.current == 0
         {
             // Fetch the ship & target position
             // Target can be despawned by a earlier weapon system. So double check
-            let Ok([ship_tran, target_tran]) = position.get_many([*ship, *target]) else {
+            let Ok([(ship_tran, _), (target_tran, target_pos)]) =
+                position.get_many([*ship, *target])
+            else {
                 continue;
             };
 
             weapon.current = weapon.cooldown;
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Shot,
+                Some(*ship),
+                Some(*target),
+                u32::from(weapon.damage),
+            ));
+
+            // Firing through our own shield drains it, whatever gets through carries on
+            let mut dmg = weapon.damage;
+            for attachment in attachments.iter().flat_map(|a| a.iter()) {
+                if let Ok((mut shield_health, shield, heading, arc)) =
+                    shield_query.get_mut(attachment)
+                    && shield_health.current > 0
+                    && matches!(
+                        within_arc(
+                            ship_pos.0.as_ivec2(),
+                            target_pos.0.as_ivec2(),
+                            heading.0,
+                            arc.current,
+                        ),
+                        ArcCheck::InsideArc
+                    )
+                {
+                    dmg = shield.absorb(&mut shield_health, arc.current, dmg);
+                    sim_event.write(SimEvent::new(
+                        ticks.now(),
+                        SimEventKind::ShieldAbsorb,
+                        Some(*ship),
+                        Some(*ship),
+                        u32::from(weapon.damage - dmg),
+                    ));
+                    break;
+                }
+            }
 
             // Setup the weapon render
-            commands.spawn(RenderDebugWeapon {
-                origin: ship_tran.translation.truncate(),
-                target: target_tran.translation.truncate(),
-                fade: Timer::new(Duration::from_secs_f32(5.), TimerMode::Once),
-            });
+            commands.spawn(Effect::beam(
+                &ticks,
+                ship_tran.translation.truncate(),
+                target_tran.translation.truncate(),
+            ));
 
             // emit damage event to the target
             commands.trigger(DamageEvent {
                 target: *target,
                 pos: ship_pos.0.as_ivec2(),
-                dmg: weapon.damage,
+                dmg,
+                source: Some(*ship),
+                cause: DamageCause::Weapon,
             });
         }
     }
@@ -275,21 +409,38 @@ pub fn process_fire_debug_weapon_message(
 pub fn process_fire_debug_warhead_message(
     mut commands: Commands,
     mut fire_debug_warhead_message: MessageReader<FireDebugWarheadMessage>,
-    have_warhead: Query<&DebugWarhead>,
+    have_warhead: Query<&DebugWarhead, Without<Destroyed>>,
     render_position: Query<&Transform>,
-    position: Query<(Entity, &Position)>,
+    position: Query<(Entity, &Position), With<Ship>>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
 ) {
+    // Script and contact can both set the warhead off in the same tick, it only goes off once
+    let mut detonated: Vec<Entity> = vec![];
+
     for FireDebugWarheadMessage(ship) in fire_debug_warhead_message.read() {
         // does this ship (self) have a warhead component?
-        if let Ok(warhead) = have_warhead.get(*ship) {
+        if let Ok(warhead) = have_warhead.get(*ship)
+            && !detonated.contains(ship)
+        {
+            detonated.push(*ship);
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Shot,
+                Some(*ship),
+                None,
+                u32::from(warhead.damage),
+            ));
+
             // Fetch the ship position
             let ship_tran = render_position.get(*ship).expect("position");
 
             // Setup the weapon render
-            commands.spawn(RenderDebugWarhead {
-                origin: ship_tran.translation.truncate(),
-                fade: Timer::new(Duration::from_secs_f32(1.), TimerMode::Once),
-            });
+            commands.spawn(Effect::blast(
+                &ticks,
+                ship_tran.translation.truncate(),
+                i64::from(DISTANCE),
+            ));
 
             // Find target in radius and then emit damage to each target within radius
             let (base_ship, base_position) = position.get(*ship).expect("postion");
@@ -303,6 +454,8 @@ pub fn process_fire_debug_warhead_message(
                         target: target_ship,
                         pos: base_position.0.as_ivec2(),
                         dmg: warhead.damage,
+                        source: Some(*ship),
+                        cause: DamageCause::Warhead,
                     });
                 }
             }
@@ -313,13 +466,82 @@ pub fn process_fire_debug_warhead_message(
     }
 }
 
+// Shields are an arc shaped sensor, any warhead that crosses an active shield gets absorbed by it.
+//
+// Warhead blasts that goes off outside of the shield are handled by `process_damage_event`
+pub fn process_shield_intercept(
+    mut commands: Commands,
+    mut collision_events: MessageReader<CollisionStart>,
+    mut shield_query: Query<(&mut Health, &AttachedTo), With<Shield>>,
+    warhead_query: Query<(&DebugWarhead, &Position, &Transform)>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
+) {
+    // A warhead can cross several shields in the same tick, only the first one gets it
+    let mut absorbed: Vec<Entity> = vec![];
+
+    for event in collision_events.read() {
+        let (shield, missile) = if shield_query.contains(event.collider1) {
+            (event.collider1, event.collider2)
+        } else if shield_query.contains(event.collider2) {
+            (event.collider2, event.collider1)
+        } else {
+            continue;
+        };
+
+        let Ok((warhead, missile_pos, missile_tran)) = warhead_query.get(missile) else {
+            continue;
+        };
+        let Ok((mut shield_health, attached_to)) = shield_query.get_mut(shield) else {
+            continue;
+        };
+
+        // Skip the missile own shield and any shield that collapsed this tick
+        if attached_to.0 == missile || shield_health.current == 0 || absorbed.contains(&missile) {
+            continue;
+        }
+        absorbed.push(missile);
+
+        // Shield soaks up the warhead, anything left over goes onto the ship
+        let dmg = warhead.damage;
+        let soaked = dmg.min(shield_health.current);
+        shield_health.current -= soaked;
+        sim_event.write(SimEvent::new(
+            ticks.now(),
+            SimEventKind::ShieldAbsorb,
+            Some(missile),
+            Some(attached_to.0),
+            u32::from(soaked),
+        ));
+
+        if dmg > soaked {
+            commands.trigger(DamageEvent {
+                target: attached_to.0,
+                pos: missile_pos.0.as_ivec2(),
+                dmg: dmg - soaked,
+                source: Some(missile),
+                cause: DamageCause::Warhead,
+            });
+        }
+
+        let origin = missile_tran.translation.truncate();
+        commands.spawn(Effect::blast(&ticks, origin, i64::from(DISTANCE)));
+        commands.spawn(Effect::shield_impact(&ticks, shield, origin));
+
+        // Warhead got expended on the shield
+        commands.entity(missile).despawn();
+    }
+}
+
 // TODO: for now hardcore various things, but we need to pass in the script to the missile
 // That or yeet the script from parent ship and copy it over
 pub fn process_fire_debug_missile_message(
     mut fire_debug_missile_message: MessageReader<FireDebugMissileMessage>,
-    mut parent_missile: Query<&mut DebugMissile>,
-    parent_ship: Query<(&Position, &Heading, &Script)>,
+    mut parent_missile: Query<&mut DebugMissile, Without<Destroyed>>,
+    parent_ship: Query<(&Position, &Heading, &Faction, &Script)>,
     mut spawn_ship: MessageWriter<SpawnMessage>,
+    ticks: Res<Ticks>,
+    mut sim_event: MessageWriter<SimEvent>,
 ) {
     for FireDebugMissileMessage(ship) in fire_debug_missile_message.read() {
         // 1. does this have a missile component if so, check if we can fire
@@ -330,7 +552,7 @@ pub fn process_fire_debug_missile_message(
 
             // 2. if yes, spawn a ship next to the parent ship
             // 3. for now yeet the script from the parent ship onto this
-            let (pos, rot, parent_script) = parent_ship.get(*ship).expect("parent");
+            let (pos, rot, faction, parent_script) = parent_ship.get(*ship).expect("parent");
 
             // Calculate the position of the future missile
             let offset =
@@ -338,29 +560,39 @@ pub fn process_fire_debug_missile_message(
 
             // 4. send it on its merry way
             let missile = ShipBuilder::new(parent_script.clone())
+                .class(ShipClass::Tiny)
+                .faction(faction.0)
                 .position(offset.x, offset.y)
                 .rotation(rot.0)
                 .velocity(0, 0)
                 .radar_arc(32)
                 .warhead(100)
+                .propellant(1000)
                 .build();
 
             spawn_ship.write(SpawnMessage(missile));
+            sim_event.write(SimEvent::new(
+                ticks.now(),
+                SimEventKind::Launch,
+                Some(*ship),
+                None,
+                0,
+            ));
         }
     }
 }
 
-// TODO: figure out collision detection with shields for misiles and other ships but
-// for now skip
-// TODO: consider how a ship can fire its weapon through the shield, maybe have it so that
-// if your own shield blocks the weapon fire it will damage your own shield and reduce the amount
-// of damage that gets through to the other ship, that could be interesting, in that you have to
-// move your shield over to fire a weapon or you can keep it up and absorb the damage from your
-// own weapon to your shield?
+// Shield is an arc shaped sensor that sits at the shield radius around the ship.
+// - Incoming warheads that crosses the arc are absorbed by the shield
+// - Firing a weapon through your own shield drains it, you either have to move your shield out of
+// the way to fire or you keep it up and eat some of the damage from your own weapon.
 #[derive(Bundle, Clone)]
 pub struct ShieldBundle {
     pub shield: Shield,
+    pub sensor: Sensor,
     pub health: Health,
+    pub regen: ShieldRegen,
+    pub module: Module,
     pub heading: Heading,
     pub target: TargetHeading,
     pub arc: ArcWidth,
@@ -372,16 +604,26 @@ impl ShieldBundle {
         target: AbsRot,
         current_arc: u8,
         target_arc: u8,
-        damage_reduce: f32,
+        curve: ShieldCurve,
+        radius: i64,
         health: u16,
     ) -> Self {
         Self {
-            // Clamped to 0-1 cuz over 1 makes the damage split underflow
-            shield: Shield { damage_reduce: damage_reduce.clamp(0.0, 1.0) },
+            shield: Shield { curve, radius },
+            sensor: Sensor,
             health: Health {
                 current: health,
                 maximum: health,
             },
+            // No regen till the ship opts into it with `ShipBuilder::shield_regen`
+            regen: ShieldRegen {
+                rate: 0,
+                carry: 0,
+                power_cost: 0,
+                recovery: 0,
+                collapsed: None,
+            },
+            module: Module::new(ModuleKind::Shield),
             heading: Heading(current),
             target: TargetHeading {
                 // NOTE: Insta rotation, can adjust later
@@ -392,6 +634,9 @@ impl ShieldBundle {
             arc: ArcWidth {
                 current: current_arc,
                 target: target_arc,
+                // Shield takes time to reshape
+                limit: 32,
+                carry: 0,
             },
         }
     }
@@ -407,8 +652,22 @@ impl ShieldBundle {
         self.arc.target = arc;
     }
 
-    pub fn damage_reduce(&mut self, damage_reduce: f32) {
-        self.shield.damage_reduce = damage_reduce.clamp(0.0, 1.0);
+    pub fn arc_limit(&mut self, limit: u16) {
+        self.arc.limit = limit;
+    }
+
+    pub fn curve(&mut self, curve: ShieldCurve) {
+        self.shield.curve = curve;
+    }
+
+    pub fn radius(&mut self, radius: i64) {
+        self.shield.radius = radius;
+    }
+
+    pub fn regen(&mut self, rate: u32, power_cost: u32, recovery: u64) {
+        self.regen.rate = rate;
+        self.regen.power_cost = power_cost;
+        self.regen.recovery = recovery;
     }
 
     pub fn health(&mut self, health: u16) {
@@ -420,8 +679,417 @@ impl ShieldBundle {
 #[derive(Component, Clone, Copy)]
 #[require(ArcWidth, Heading, Health)]
 pub struct Shield {
-    damage_reduce: f32,
+    curve: ShieldCurve,
+    // Distance of the shield arc from the ship center
+    radius: i64,
+}
+
+impl Shield {
+    pub const fn radius(&self) -> i64 {
+        self.radius
+    }
+
+    // Split the incoming damage between the shield and the ship, returns the damage that gets
+    // through. Once the shield pool runs dry the rest of the damage gets through as well.
+    pub fn absorb(&self, health: &mut Health, half_arc: u8, dmg: u16) -> u16 {
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let shield_damage: u16 =
+            (f32::from(dmg) * self.curve.damage_reduce(half_arc)).round() as u16;
+
+        // If shield can't cover full shield damage, deduce and pass on to ship
+        if let Some(new_shield_health) = health.current.checked_sub(shield_damage) {
+            health.current = new_shield_health;
+            dmg - shield_damage
+        } else {
+            // Can't cover full damage, deduce what we can and pass it on
+            let carry_shield_damage = shield_damage - health.current;
+            health.current = 0;
+            dmg - shield_damage + carry_shield_damage
+        }
+    }
+}
+
+// Shield damage reduction depends on how wide the shield is spread:
+// - A pinpoint shield (half-arc 0) reduces `pinpoint` of the damage
+// - A full shield (half-arc 127) reduces `full` of the damage
+// - `exponent` shapes the falloff in between, 1.0 is linear, > 1.0 holds onto the pinpoint
+//   reduction for longer as the shield widens
+#[derive(Debug, Clone, Copy)]
+pub struct ShieldCurve {
+    pinpoint: f32,
+    full: f32,
+    exponent: f32,
+}
+
+impl Default for ShieldCurve {
+    fn default() -> Self {
+        Self::new(0.95, 0.05, 1.0)
+    }
+}
+
+impl ShieldCurve {
+    pub fn new(pinpoint: f32, full: f32, exponent: f32) -> Self {
+        // Clamped to 0-1 cuz over 1 makes the damage split underflow
+        Self {
+            pinpoint: pinpoint.clamp(0.0, 1.0),
+            full: full.clamp(0.0, 1.0),
+            exponent: exponent.max(0.0),
+        }
+    }
+
+    pub fn damage_reduce(&self, half_arc: u8) -> f32 {
+        let width = f32::from(half_arc.min(127)) / 127.0;
+        (self.pinpoint + (self.full - self.pinpoint) * width.powf(self.exponent)).clamp(0.0, 1.0)
+    }
+}
+
+// Shield regeneration, once the shield collapses it stays down for `recovery` ticks
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShieldRegen {
+    // Shield health per second
+    pub rate: u32,
+    // Sub tick regeneration
+    pub carry: u32,
+    // Power per point of shield health, 0 == free
+    pub power_cost: u32,
+    pub recovery: u64,
+    // Tick the shield collapsed on, held till the first point of health is back
+    pub collapsed: Option<u64>,
+}
+
+#[expect(clippy::needless_pass_by_value)]
+pub(crate) fn apply_shield_regen(
+    ticks: Res<Ticks>,
+    mut query: Query<(&mut Health, &mut ShieldRegen, &AttachedTo), With<Shield>>,
+    mut power_query: Query<&mut Power>,
+) {
+    for (mut health, mut regen, attached_to) in query.iter_mut() {
+        // Keep a collapsed shield down till it recovers, it only collapses again once it has
+        // been back up
+        if health.current > 0 {
+            if regen.collapsed.is_some() {
+                regen.collapsed = None;
+            }
+        } else if regen.collapsed.is_none() {
+            regen.collapsed = Some(ticks.now());
+        }
+        if let Some(start) = regen.collapsed
+            && !ticks.is_ready(start, regen.recovery)
+        {
+            continue;
+        }
+
+        // Shield is full, drop the carry
+        if health.current >= health.maximum {
+            if regen.carry != 0 {
+                regen.carry = 0;
+            }
+            continue;
+        }
+
+        let (step, carry) = tick_step(regen.rate, regen.carry, TICK_HZ);
+        regen.carry = carry;
+
+        let amount = u16::try_from(step)
+            .unwrap_or(u16::MAX)
+            .min(health.maximum - health.current);
+        if amount == 0 {
+            continue;
+        }
+
+        // No power, no regen
+        if regen.power_cost > 0 {
+            let Ok(mut power) = power_query.get_mut(attached_to.0) else {
+                continue;
+            };
+            if !power.draw(u32::from(amount) * regen.power_cost) {
+                continue;
+            }
+        }
+        health.current += amount;
+    }
+}
+
+// Polyline along the shield edge in local space (+Y == shield heading)
+pub fn shield_collider(half_arc: u8, radius: i64) -> Collider {
+    // Polyline needs at least 2 points, so pinpoint shields gets widened a bit
+    let half_arc = half_arc.clamp(1, 127).cast_signed();
+
+    let vertices = (-half_arc..=half_arc)
+        .map(|step| {
+            let edge = (AbsRot(0) + RelRot(step)).to_heading_fp().as_i64vec2();
+            (edge * radius / FP_SCALE).as_vec2()
+        })
+        .collect();
+    Collider::polyline(vertices, None)
+}
+
+// Rebuild the shield collider upon arc changes, and turn it off when the shield is down
+#[expect(clippy::type_complexity)]
+pub(crate) fn apply_shield_collider(
+    mut commands: Commands,
+    query: Query<
+        (Entity, &Shield, Ref<ArcWidth>, &Health),
+        Or<(Changed<ArcWidth>, Changed<Health>)>,
+    >,
+) {
+    for (entity, shield, arc, health) in query.iter() {
+        let radius = shield.radius;
+        let mut shield = commands.entity(entity);
+
+        if arc.is_changed() {
+            shield.insert(shield_collider(arc.current, radius));
+        }
+        if health.current == 0 {
+            shield.insert(ColliderDisabled);
+        } else {
+            shield.remove::<ColliderDisabled>();
+        }
+    }
 }
 
 #[derive(Component, Clone, Copy)]
 pub struct ShieldHealthDebug;
+
+#[test]
+fn test_shield_absorb() {
+    // Flat curve, arc width does not matter
+    let shield = Shield {
+        curve: ShieldCurve::new(0.75, 0.75, 1.0),
+        radius: 300,
+    };
+    let mut health = Health {
+        current: 100,
+        maximum: 100,
+    };
+
+    // Shield covers 3/4 of the damage
+    assert_eq!(shield.absorb(&mut health, 32, 40), 10);
+    assert_eq!(health.current, 70);
+
+    // Shield can only cover 70 of the 75, rest carries over
+    assert_eq!(shield.absorb(&mut health, 0, 100), 30);
+    assert_eq!(health.current, 0);
+
+    // Shield is down, everything gets through
+    assert_eq!(shield.absorb(&mut health, 127, 20), 20);
+    assert_eq!(health.current, 0);
+}
+
+#[expect(clippy::float_cmp)]
+#[test]
+fn test_shield_curve() {
+    let linear = ShieldCurve::new(1.0, 0.0, 1.0);
+    assert_eq!(linear.damage_reduce(0), 1.0);
+    assert_eq!(linear.damage_reduce(127), 0.0);
+    assert!((linear.damage_reduce(64) - 0.496).abs() < 1e-3);
+
+    // Out of bound arcs are clamped
+    assert_eq!(linear.damage_reduce(255), 0.0);
+
+    // Steeper curve holds onto the reduction for longer
+    let steep = ShieldCurve::new(1.0, 0.0, 2.0);
+    assert!(steep.damage_reduce(64) > linear.damage_reduce(64));
+    assert_eq!(steep.damage_reduce(127), 0.0);
+}
+
+#[test]
+fn test_shield_rotated_intercept() {
+    use avian2d::prelude::Rotation;
+
+    use crate::ship::Loadout;
+    use crate::ship::add_ship;
+    use crate::testing::idle;
+    use crate::testing::sim_app;
+
+    let mut app = sim_app();
+
+    // Shield starts out facing north
+    let ship = ShipBuilder::new(idle())
+        .loadout(Loadout::new().weapon(false))
+        .shield(AbsRot(0))
+        .shield_health(100)
+        .build();
+    let ship = add_ship(&mut app.world_mut().commands(), ship);
+    app.update();
+    app.update();
+
+    // Swing it around to the south
+    let shield = app
+        .world_mut()
+        .query_filtered::<(Entity, &AttachedTo), With<Shield>>()
+        .iter(app.world())
+        .find(|(_, attached_to)| attached_to.0 == ship)
+        .map(|(entity, _)| entity)
+        .expect("shield");
+    app.world_mut()
+        .get_mut::<TargetHeading>(shield)
+        .expect("target")
+        .target = AbsRot(128);
+    for _ in 0..4 {
+        app.update();
+    }
+    let rotation = app.world().get::<Rotation>(shield).expect("rotation");
+    assert_eq!(*rotation, AbsRot(128).to_rotation());
+
+    // Warhead coming in from the south
+    let missile = ShipBuilder::new(idle())
+        .class(ShipClass::Tiny)
+        .faction(1)
+        .loadout(Loadout::new().shield(false))
+        .position(0, -1000)
+        .velocity(0, 250)
+        .warhead(50)
+        .build();
+    let missile = add_ship(&mut app.world_mut().commands(), missile);
+    for _ in 0..320 {
+        app.update();
+        if app.world().get_entity(missile).is_err() {
+            break;
+        }
+    }
+    assert!(
+        app.world().get_entity(missile).is_err(),
+        "warhead never went off"
+    );
+
+    // Shield soaked up the whole warhead, the hull didn't take any of it
+    let shield_health = app.world().get::<Health>(shield).expect("health");
+    assert_eq!(shield_health.current, 50);
+    let health = app.world().get::<Health>(ship).expect("health");
+    assert_eq!(health.current, health.maximum);
+}
+
+#[test]
+fn test_damage_event_hits() {
+    use crate::time::TimeControlPlugin;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TimeControlPlugin))
+        .add_message::<SimEvent>()
+        .add_observer(process_damage_event);
+
+    let shooter = app.world_mut().spawn_empty().id();
+    let target = app
+        .world_mut()
+        .spawn((
+            Health {
+                current: 100,
+                maximum: 100,
+            },
+            Position(Vec2::ZERO),
+            Heading(AbsRot(0)),
+        ))
+        .id();
+
+    for (dmg, cause) in [
+        (10, DamageCause::Weapon),
+        (10, DamageCause::Impact),
+        // Nothing got through, no hit
+        (0, DamageCause::Weapon),
+        // Rammed to death, no kill credit either
+        (80, DamageCause::Impact),
+    ] {
+        app.world_mut().trigger(DamageEvent {
+            target,
+            pos: IVec2::new(0, 100),
+            dmg,
+            source: Some(shooter),
+            cause,
+        });
+    }
+
+    let hits: Vec<(SimEventKind, Option<Entity>, u32)> = app
+        .world_mut()
+        .resource_mut::<Messages<SimEvent>>()
+        .drain()
+        .map(|event| (event.kind, event.source, event.amount))
+        .collect();
+    // Ramming damage is only credited as a collision
+    assert_eq!(
+        hits,
+        [
+            (SimEventKind::Hit, Some(shooter), 10),
+            (SimEventKind::Hit, None, 10),
+            (SimEventKind::Hit, None, 80),
+            (SimEventKind::Kill, None, 80),
+        ]
+    );
+    assert_eq!(
+        app.world().get::<Health>(target).expect("health").current,
+        0
+    );
+}
+
+#[test]
+fn test_apply_shield_regen() {
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    use crate::time::TimeControlPlugin;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TimeControlPlugin))
+        .add_systems(FixedUpdate, apply_shield_regen)
+        .insert_resource(Time::<Fixed>::from_hz(f64::from(TICK_HZ)))
+        // One tick per update
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / f64::from(TICK_HZ),
+        )));
+
+    // Ship with the power left in its bank, and its shield at the health
+    let mut shield = |power: u32, health: u16, rate: u32, power_cost: u32, recovery: u64| {
+        let ship = app.world_mut().spawn(Power::new(power, 0)).id();
+        let mut bundle = ShieldBundle::new(
+            AbsRot(0),
+            AbsRot(0),
+            64,
+            64,
+            ShieldCurve::default(),
+            100,
+            100,
+        );
+        bundle.regen(rate, power_cost, recovery);
+        bundle.health.current = health;
+        app.world_mut().spawn((bundle, AttachedTo(ship))).id()
+    };
+    // 1 point per tick
+    let steady = shield(0, 50, 64, 0, 0);
+    // 2 power per point, runs dry after 5 points
+    let costly = shield(10, 50, 64, 2, 0);
+    // 1 point every 8 ticks once the 256 ticks are up
+    let collapsed = shield(0, 0, 8, 0, 256);
+    // No power to come back up with
+    let starved = shield(0, 0, 64, 2, 4);
+
+    let health = |app: &App, shield| app.world().get::<Health>(shield).expect("health").current;
+    let now = |app: &App| app.world().resource::<Ticks>().now();
+    let start = now(&app);
+
+    while now(&app) < start + 10 {
+        app.update();
+    }
+    assert_eq!(health(&app, steady), 60);
+    assert_eq!(health(&app, costly), 55);
+    let ship = app.world().get::<AttachedTo>(costly).expect("ship").0;
+    assert_eq!(app.world().get::<Power>(ship).expect("power").current, 0);
+
+    // Collapsed on the first tick, stays down the whole recovery then the carry builds up to the
+    // first point over 8 ticks
+    while now(&app) < start + 263 {
+        app.update();
+        assert_eq!(health(&app, collapsed), 0);
+    }
+    app.update();
+    assert_eq!(health(&app, collapsed), 1);
+
+    // Recovery is long over, once the power is back it regens right away
+    assert_eq!(health(&app, starved), 0);
+    let ship = app.world().get::<AttachedTo>(starved).expect("ship").0;
+    app.world_mut()
+        .get_mut::<Power>(ship)
+        .expect("power")
+        .current = 100;
+    app.update();
+    assert_eq!(health(&app, starved), 1);
+}
@@ -39,7 +39,7 @@ fn setup_binary_code(test_asm: &str, rom: &mut [u8; 4096], full: bool) {
 
 fn run_rom(rom: [u8; 4096]) {
     let mut vm = riscv::vm::Emul32::new_with_rom(rom);
-    vm.run();
+    vm.run(|_, _| [0, 0, 0]);
 }
 
 
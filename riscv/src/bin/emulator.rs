@@ -12,6 +12,7 @@ fn main() {
     // Virtal machine run
     loop {
         vm.set_pc(0);
-        vm.run();
+        // No host services yet, every syscall gets 0 back
+        vm.run(|_, _| [0, 0, 0]);
     }
 }
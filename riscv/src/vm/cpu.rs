@@ -318,7 +318,7 @@ impl Cpu {
             },
 
             // RV32 I
-            // TODO: finish implementing these (particularly ECALL)
+            // TODO: finish implementing these
             // - Implement MRET for returning from trap
             // - Implement WFI - can make the emulator halt the cpu portion till a interrupt
             // fires, this depends on how we do it, if its essental, we can NOP instead -> busy
@@ -330,7 +330,9 @@ impl Cpu {
                 match imm {
                     0b0000_0000_0000 => {
                         // ECALL
-                        // NOP instruction
+                        // Hand it off to the environment, resume after the ECALL
+                        self.pc += 4;
+                        return Err(Trap::EnvironmentCall);
                     },
                     0b0000_0000_0001 => {
                         // EBREAK
@@ -48,6 +48,8 @@ pub enum Trap {
     IllegalMemoryAccess(u32),
     UnalignedInstructionAccess(u32),
     InterruptTimer,
+    // Service request to the host, see Emul32::ecall
+    EnvironmentCall,
 }
 
 
@@ -105,11 +107,14 @@ impl Emul32 {
         }
     }
 
-    pub fn run(&mut self) {
+    // Runs till the first fault, the ECALLs are handed off to the handler (see Emul32::ecall)
+    // and the program resumes after them
+    pub fn run(&mut self, mut handler: impl FnMut(u32, [u32; 6]) -> [u32; 3]) -> Trap {
         loop {
             match self.step() {
                 Ok(_)  => (),
-                Err(_) => break,
+                Err(Trap::EnvironmentCall) => self.ecall(&mut handler),
+                Err(trap) => return trap,
             }
         }
     }
@@ -131,6 +136,19 @@ impl Emul32 {
     pub fn set_pc(&mut self, pc: u32) {
         self.cpu.set_pc(pc);
     }
+
+    // Service an EnvironmentCall trap
+    // - syscall number in a7 (x17), arguments in a0-a5 (x10-x15)
+    // - result goes back into a0-a2 (x10-x12)
+    pub fn ecall(&mut self, handler: impl FnOnce(u32, [u32; 6]) -> [u32; 3]) {
+        let reg = &mut self.cpu.reg;
+        let args = [reg[10], reg[11], reg[12], reg[13], reg[14], reg[15]];
+
+        let [a0, a1, a2] = handler(reg[17], args);
+        reg[10] = a0;
+        reg[11] = a1;
+        reg[12] = a2;
+    }
 }
 
 
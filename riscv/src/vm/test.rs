@@ -27,6 +27,11 @@ mod op_tests {
         rom
     }
 
+    // None of the instruction tests should be making any syscalls
+    fn no_ecall(number: u32, _args: [u32; 6]) -> [u32; 3] {
+        panic!("unexpected ecall {}", number)
+    }
+
     mod rr_op_tests {
         use super::*;
 
@@ -62,7 +67,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[3], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], a);
@@ -79,7 +84,7 @@ mod op_tests {
             vm.cpu.reg[2] = b;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], res);
@@ -95,7 +100,7 @@ mod op_tests {
             vm.cpu.reg[2] = b;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], a);
@@ -110,7 +115,7 @@ mod op_tests {
             vm.cpu.reg[1] = a;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], res);
@@ -127,7 +132,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[1], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -146,7 +151,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[1], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -162,7 +167,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[1], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -178,7 +183,7 @@ mod op_tests {
             vm.cpu.reg[2] = b;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -232,7 +237,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[2], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], a);
@@ -247,7 +252,7 @@ mod op_tests {
             vm.cpu.reg[1] = a;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], res);
@@ -261,7 +266,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[2], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -276,7 +281,7 @@ mod op_tests {
             vm.cpu.reg[1] = a;
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[0], 0);
@@ -349,7 +354,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[10], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], val1);
@@ -402,7 +407,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[10], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], val1);
@@ -443,7 +448,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[1], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], 0x8); // AUIPC is at PC=0, 2f is at 0x8
@@ -486,7 +491,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[11], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate - want to skip over x3, x5, x7
             assert_eq!(vm.cpu.reg[1], 0x1);
@@ -524,7 +529,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[5], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], 0x1);
@@ -576,7 +581,7 @@ mod op_tests {
             assert_eq!(vm.cpu.reg[1], 0);
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[1], res);
@@ -627,7 +632,7 @@ mod op_tests {
             );
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[2], res);
@@ -676,7 +681,7 @@ mod op_tests {
             );
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[2], res);
@@ -788,7 +793,7 @@ mod op_tests {
             );
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[3], res);
@@ -820,7 +825,7 @@ mod op_tests {
             );
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[3], res2);
@@ -865,7 +870,7 @@ mod op_tests {
             );
 
             // Run
-            vm.run();
+            vm.run(no_ecall);
 
             // Validate
             assert_eq!(vm.cpu.reg[3], res2);
@@ -902,6 +907,63 @@ mod op_tests {
         }
     }
 
+    mod system_tests {
+        use super::*;
+
+        #[test]
+        fn test_ecall() {
+            // The assembler doesn't support ECALL, splice it in by hand
+            let mut rom = generate_rom("addi x17 x0 5\naddi x10 x0 7\naddi x11 x0 3");
+            let after = generate_rom("addi x12 x10 0");
+            rom[12..16].copy_from_slice(&0x0000_0073_u32.to_le_bytes());
+            rom[16..20].copy_from_slice(&after[0..4]);
+
+            let mut vm = Emul32::new_with_rom(rom);
+            for _ in 0..3 {
+                assert_eq!(vm.step(), Ok(()));
+            }
+            assert_eq!(vm.step(), Err(Trap::EnvironmentCall));
+
+            vm.ecall(|number, args| {
+                assert_eq!(number, 5);
+                assert_eq!(args, [7, 3, 0, 0, 0, 0]);
+                [args[0] + args[1], 1, 2]
+            });
+            assert_eq!(vm.cpu.reg[12], 2);
+
+            // Resumes after the ECALL
+            assert_eq!(vm.step(), Ok(()));
+            assert_eq!(vm.cpu.reg[10], 10);
+            assert_eq!(vm.cpu.reg[11], 1);
+            assert_eq!(vm.cpu.reg[12], 10);
+        }
+
+        #[test]
+        fn test_run_ecall() {
+            let mut rom = generate_rom("addi x17 x0 5\naddi x10 x0 7\naddi x11 x0 3");
+            let after = generate_rom("addi x13 x10 0\naddi x17 x0 6");
+            rom[12..16].copy_from_slice(&0x0000_0073_u32.to_le_bytes());
+            rom[16..24].copy_from_slice(&after[0..8]);
+            rom[24..28].copy_from_slice(&0x0000_0073_u32.to_le_bytes());
+
+            // Services both ECALLs, the unknown one gets an error code back, then stops on the
+            // empty rom after the program
+            let mut vm = Emul32::new_with_rom(rom);
+            let mut calls = vec![];
+            let trap = vm.run(|number, args| {
+                calls.push(number);
+                match number {
+                    5 => [args[0] + args[1], 0, 0],
+                    _ => [(-38_i32) as u32, 0, 0],
+                }
+            });
+            assert_eq!(trap, Trap::IllegalInstruction(0));
+            assert_eq!(calls, [5, 6]);
+            assert_eq!(vm.cpu.reg[13], 10);
+            assert_eq!(vm.cpu.reg[10], (-38_i32) as u32);
+        }
+    }
+
 // COUNTERS (CSR)
 //
 // Haven't found a way to make usable:
@@ -909,5 +971,5 @@ mod op_tests {
 //
 // Won't implement:
 // SYNCH (fence)
-// SYSTEM (scall/sbreak)/(ebreak/ecall)
+// SYSTEM (ebreak)
 }